@@ -0,0 +1,103 @@
+//! The answers which the parts of a puzzle give.
+
+use std::fmt::Display;
+
+/// The answer to one part of a puzzle.
+///
+/// Integers are always stored in the smallest variant which fits them (which the [`From`]
+/// implementations take care of), so two answers with the same value compare as equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(i128),
+    Text(String),
+}
+
+impl Answer {
+    /// The answer as an integer, or [`Option::None`] if it's text.
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Answer::Integer(n) => Some(i128::from(*n)),
+            Answer::BigInteger(n) => Some(*n),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::BigInteger(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        match i64::try_from(n) {
+            Ok(n) => Answer::Integer(n),
+            Err(_) => Answer::BigInteger(n),
+        }
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Answer::from(n),
+            // This is too big for us to do arithmetic with, but we can still show it.
+            Err(_) => Answer::Text(n.to_string()),
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::from(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_owned())
+    }
+}
+
+impl PartialEq<i64> for Answer {
+    fn eq(&self, other: &i64) -> bool {
+        self.as_integer() == Some(i128::from(*other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_normalised() {
+        assert_eq!(Answer::from(5_usize), Answer::from(5_i128));
+        assert_eq!(Answer::from(5_u8), Answer::Integer(5));
+        assert_eq!(
+            Answer::from(i128::from(i64::MAX) + 1).as_integer(),
+            Some(1 << 63)
+        );
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from(-3_i32), -3);
+    }
+}
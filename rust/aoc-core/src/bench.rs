@@ -0,0 +1,20 @@
+//! Helpers for benchmarking a [`Solution`] with criterion.
+
+use std::hint::black_box;
+
+use criterion::Criterion;
+
+use crate::Solution;
+
+/// Benchmark parsing and both parts of a solution on one input.
+///
+/// The benchmarks are named `dayNN/<input_name>/parse`, `dayNN/<input_name>/part1` and
+/// `dayNN/<input_name>/part2`, so that `cargo bench -- dayNN/<input_name>` picks out one input.
+pub fn bench_solution<S: Solution>(c: &mut Criterion, input_name: &str, input: &str) {
+    let mut group = c.benchmark_group(format!("day{:02}/{input_name}", S::DAY));
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(input))));
+    let parsed = S::parse(input).expect("We only benchmark valid inputs.");
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+    group.finish();
+}
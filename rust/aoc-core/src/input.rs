@@ -0,0 +1,257 @@
+//! Working out where the puzzle inputs come from.
+//!
+//! Everyone has a different puzzle input, so rather than redirecting files into stdin, the inputs
+//! can be named on the command line or left in an `inputs/` directory. In order, we use:
+//!
+//! 1. the files given with `--input` or as positional arguments,
+//! 2. stdin, if a pipe or a file with something in it is redirected into it,
+//! 3. `inputs/dayNN.txt` and every `inputs/dayNN/*.txt`.
+//!
+//! Cron jobs, CI runners and IDEs often leave stdin as `/dev/null` or an empty pipe, which we
+//! don't want to count as an empty input, so anything else on stdin is ignored.
+
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+/// The directory, relative to the working directory, where inputs are looked for by default.
+pub const INPUTS_DIR: &str = "inputs";
+
+/// Somewhere to read a puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            Source::Stdin => std::io::read_to_string(std::io::stdin())
+                .map_err(|e| InputError::Io(self.clone(), e)),
+            Source::File(path) => {
+                std::fs::read_to_string(path).map_err(|e| InputError::Io(self.clone(), e))
+            }
+        }
+    }
+
+    /// Open the input to read it a bit at a time, for inputs which are too big to read all at
+    /// once.
+    pub fn open(&self) -> Result<Box<dyn BufRead>, InputError> {
+        match self {
+            Source::Stdin => Ok(Box::new(std::io::stdin().lock())),
+            Source::File(path) => match File::open(path) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(e) => Err(InputError::Io(self.clone(), e)),
+            },
+        }
+    }
+
+    /// The file this is, or [`Option::None`] for stdin.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Source::Stdin => None,
+            Source::File(path) => Some(path),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "stdin"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// Nothing was given, nothing was piped in, and there's nothing in the inputs directory.
+    NoInput {
+        day: u8,
+    },
+    Io(Source, std::io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NoInput { day } => write!(
+                f,
+                "there's no input for day {day}: pass a file, pipe one in, or put it at \
+                {INPUTS_DIR}/day{day:02}.txt"
+            ),
+            InputError::Io(source, e) => write!(f, "could not read {source}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// The command line arguments for choosing the inputs, which every binary shares.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct InputArgs {
+    /// Read the puzzle input from this file. Can be given more than once.
+    #[arg(long = "input", value_name = "FILE")]
+    pub inputs: Vec<PathBuf>,
+    /// More input files. If no files are given and nothing is piped in, the input is read from
+    /// `inputs/dayNN.txt` and `inputs/dayNN/*.txt`.
+    #[arg(value_name = "FILES")]
+    pub files: Vec<PathBuf>,
+}
+
+impl InputArgs {
+    /// Every input to run the given day on, in the order they should be run.
+    pub fn sources(&self, day: u8) -> Result<Vec<Source>, InputError> {
+        let mut stdin = std::io::stdin().lock();
+        let piped = is_piped(&std::io::stdin()).then_some(&mut stdin as &mut dyn BufRead);
+        self.sources_in(Path::new(INPUTS_DIR), day, piped)
+    }
+
+    /// Like [`InputArgs::sources`], with the default inputs in `dir`, and `stdin` only if it's a
+    /// pipe or a file.
+    fn sources_in(
+        &self,
+        dir: &Path,
+        day: u8,
+        stdin: Option<&mut dyn BufRead>,
+    ) -> Result<Vec<Source>, InputError> {
+        let named: Vec<Source> = self
+            .inputs
+            .iter()
+            .chain(&self.files)
+            .map(|path| Source::File(path.clone()))
+            .collect();
+        if !named.is_empty() {
+            return Ok(named);
+        }
+        if let Some(stdin) = stdin {
+            // Looking for something to read leaves it in stdin's buffer, so it's still there to
+            // be read properly later.
+            let buffer = stdin
+                .fill_buf()
+                .map_err(|e| InputError::Io(Source::Stdin, e))?;
+            if !buffer.is_empty() {
+                return Ok(vec![Source::Stdin]);
+            }
+        }
+        default_sources(dir, day)
+    }
+}
+
+/// Whether stdin is a pipe or a file, rather than a terminal or a device like `/dev/null`.
+#[cfg(unix)]
+fn is_piped(stdin: &std::io::Stdin) -> bool {
+    use std::os::fd::AsFd;
+    use std::os::unix::fs::FileTypeExt;
+
+    let Ok(fd) = stdin.as_fd().try_clone_to_owned() else {
+        return false;
+    };
+    File::from(fd)
+        .metadata()
+        .is_ok_and(|metadata| metadata.file_type().is_fifo() || metadata.is_file())
+}
+
+/// Whether stdin is anything but a terminal, since we can't easily tell pipes from devices here.
+/// An empty stdin is still ignored.
+#[cfg(not(unix))]
+fn is_piped(stdin: &std::io::Stdin) -> bool {
+    use std::io::IsTerminal;
+
+    !stdin.is_terminal()
+}
+
+/// The inputs for a day in an inputs directory: `dayNN.txt`, followed by every `dayNN/*.txt` in
+/// order of their names. It's an error if there aren't any.
+pub fn default_sources(dir: &Path, day: u8) -> Result<Vec<Source>, InputError> {
+    let mut sources: Vec<Source> = Vec::new();
+
+    let single = dir.join(format!("day{day:02}.txt"));
+    if single.is_file() {
+        sources.push(Source::File(single));
+    }
+
+    let day_dir = dir.join(format!("day{day:02}"));
+    if day_dir.is_dir() {
+        let entries =
+            std::fs::read_dir(&day_dir).map_err(|e| InputError::Io(Source::File(day_dir), e))?;
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"))
+            .collect();
+        files.sort();
+        sources.extend(files.into_iter().map(Source::File));
+    }
+
+    if sources.is_empty() {
+        Err(InputError::NoInput { day })
+    } else {
+        Ok(sources)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_lookup() {
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("day02")).unwrap();
+        for file in [
+            "day01.txt",
+            "day02.txt",
+            "day02/b.txt",
+            "day02/a.txt",
+            "day02/notes.md",
+        ] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+
+        let sources = default_sources(&dir, 2);
+        let expected =
+            ["day02.txt", "day02/a.txt", "day02/b.txt"].map(|f| Source::File(dir.join(f)));
+        assert_eq!(sources.unwrap(), expected);
+        assert_eq!(
+            default_sources(&dir, 1).unwrap(),
+            [Source::File(dir.join("day01.txt"))]
+        );
+        assert!(matches!(
+            default_sources(&dir, 3),
+            Err(InputError::NoInput { day: 3 })
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn empty_stdin() {
+        let dir = std::env::temp_dir().join(format!("aoc-stdin-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day01.txt"), "").unwrap();
+        let args = InputArgs::default();
+        let default = [Source::File(dir.join("day01.txt"))];
+
+        let mut empty: &[u8] = b"";
+        let sources = args.sources_in(&dir, 1, Some(&mut empty)).unwrap();
+        assert_eq!(sources, default);
+        assert_eq!(args.sources_in(&dir, 1, None).unwrap(), default);
+        let mut piped: &[u8] = b"3   4\n";
+        let sources = args.sources_in(&dir, 1, Some(&mut piped)).unwrap();
+        assert_eq!(sources, [Source::Stdin]);
+        assert_eq!(piped, b"3   4\n");
+
+        let named = InputArgs {
+            files: vec![PathBuf::from("lists.txt")],
+            ..InputArgs::default()
+        };
+        let mut piped: &[u8] = b"3   4\n";
+        let sources = named.sources_in(&dir, 1, Some(&mut piped)).unwrap();
+        assert_eq!(sources, [Source::File(PathBuf::from("lists.txt"))]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
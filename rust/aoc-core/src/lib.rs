@@ -0,0 +1,209 @@
+//! The pieces shared by every day's solution.
+
+mod answer;
+#[cfg(feature = "criterion")]
+pub mod bench;
+pub mod input;
+pub mod measure;
+pub mod normalise;
+mod parse;
+
+use std::fmt::Display;
+use std::process::ExitCode;
+
+pub use answer::Answer;
+pub use input::{InputArgs, InputError, Source};
+pub use normalise::normalise;
+pub use parse::{parse_token, Found, ParseError};
+
+/// One of the two parts of a day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Both parts, in the order they should be run.
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl TryFrom<u8> for Part {
+    type Error = u8;
+
+    /// Get the part from its number, giving the number back if it isn't 1 or 2.
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        match n {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(n),
+        }
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A solution to one day's puzzle.
+///
+/// The puzzle input is parsed once with [`Solution::parse`], and then both parts work on the
+/// parsed input. This lets runners, benchmarks and tests treat every day the same way.
+pub trait Solution {
+    /// The day of the advent calendar which this solves.
+    const DAY: u8;
+
+    /// The parsed puzzle input.
+    type Input;
+
+    /// Parse the raw puzzle input, returning an error which points at the problem if it isn't
+    /// valid.
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError>;
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError>;
+
+    /// Run one part on an already parsed input.
+    fn solve(input: &Self::Input, part: Part) -> Result<Answer, SolveError> {
+        match part {
+            Part::One => Self::part1(input),
+            Part::Two => Self::part2(input),
+        }
+    }
+}
+
+/// Why a part couldn't get an answer from an input which did parse, like an answer which is too
+/// big for the type it's worked out in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError(pub String);
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// The command line of every day's own binary.
+#[derive(clap::Parser)]
+struct DayCli {
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+/// Work out the puzzle inputs from the command line, and print the answers to both parts for
+/// each of them.
+///
+/// This is what every day's own binary does, unless it has extra options of its own, in which
+/// case it can use [`for_each_input`] and [`print_answers`] itself.
+pub fn main<S: Solution>() -> ExitCode {
+    use clap::{CommandFactory, FromArgMatches};
+
+    let matches = DayCli::command()
+        .name(format!("day{:02}", S::DAY))
+        .about(format!("Solves day {} of Advent of Code 2024.", S::DAY))
+        .get_matches();
+    let cli = DayCli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    for_each_input(S::DAY, &cli.input, print_answers::<S>)
+}
+
+/// Read every input for a day and call `f` with each one, which should return whether it managed
+/// to do what it had to.
+///
+/// See [`input`] for where the inputs come from. Every input is cleaned up with [`normalise`]
+/// first, with a warning if that changed it. If there's more than one input, each one's output
+/// starts with its name. If an input can't be read or `f` fails on it, we carry on with the rest
+/// and return a failing exit code at the end.
+pub fn for_each_input(
+    day: u8,
+    inputs: &InputArgs,
+    mut f: impl FnMut(&Source, &str) -> bool,
+) -> ExitCode {
+    for_each_source(day, inputs, |source| {
+        let raw = match source.read() {
+            Ok(raw) => raw,
+            Err(e) => {
+                eprintln!("error: {e}");
+                return false;
+            }
+        };
+        let (input, changes) = normalise(&raw);
+        if !changes.is_empty() {
+            eprintln!("warning: {source}: {changes}");
+        }
+        f(source, &input)
+    })
+}
+
+/// Like [`for_each_input`], but leaves reading each input up to `f`, for inputs which are too big
+/// to read all at once. Nothing is normalised.
+pub fn for_each_source(
+    day: u8,
+    inputs: &InputArgs,
+    mut f: impl FnMut(&Source) -> bool,
+) -> ExitCode {
+    let sources = match inputs.sources(day) {
+        Ok(sources) => sources,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failed = false;
+    for (i, source) in sources.iter().enumerate() {
+        if sources.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("{source}:");
+        }
+        if !f(source) {
+            failed = true;
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Parse an input and print the answers to both parts, or print where it doesn't parse or why a
+/// part has no answer. Returns whether both parts got an answer.
+pub fn print_answers<S: Solution>(source: &Source, input: &str) -> bool {
+    let parsed = match S::parse(input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("error: {source}: {}", e.diagnostic(input));
+            return false;
+        }
+    };
+    let mut solved = true;
+    for part in Part::BOTH {
+        match S::solve(&parsed, part) {
+            Ok(answer) => println!("Part {part}: {answer}"),
+            Err(e) => {
+                eprintln!("error: {source}: part {part}: {e}");
+                solved = false;
+            }
+        }
+    }
+    solved
+}
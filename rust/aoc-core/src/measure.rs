@@ -0,0 +1,90 @@
+//! Measuring how long each phase of a solution takes and how much memory it uses.
+//!
+//! Memory is counted by [`CountingAllocator`], which only knows about allocations if it's been
+//! installed as the global allocator. The `aoc` binary does this; anything else which wants memory
+//! readings, like a test, has to do it too, otherwise every reading is zero.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// The number of bytes currently allocated on the heap.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+/// The most bytes which have been allocated at once since the last reset.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator which passes everything on to the [`System`] allocator, while keeping
+/// count of the heap usage.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn grew(by: usize) {
+        let now = CURRENT.fetch_add(by, Ordering::Relaxed) + by;
+        PEAK.fetch_max(now, Ordering::Relaxed);
+    }
+
+    fn shrank(by: usize) {
+        CURRENT.fetch_sub(by, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::shrank(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                Self::grew(new_size - layout.size());
+            } else {
+                Self::shrank(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// How one phase of a run went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub duration: Duration,
+    /// The most heap memory in use at once during the phase, on top of what was already in use
+    /// when it started.
+    pub peak_heap: usize,
+}
+
+/// Run `f`, measuring how long it takes and how much heap memory it uses.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Measurement) {
+    let base = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let start = Instant::now();
+    let result = f();
+    let duration = start.elapsed();
+    let peak_heap = PEAK.load(Ordering::Relaxed).saturating_sub(base);
+    (
+        result,
+        Measurement {
+            duration,
+            peak_heap,
+        },
+    )
+}
@@ -0,0 +1,138 @@
+//! Cleaning up puzzle inputs before they're parsed.
+//!
+//! Inputs which have been through Windows or an editor can end up with CRLF line endings, trailing
+//! spaces or a byte order mark. None of these ever mean anything in a puzzle, but they do break
+//! parsers which look for empty lines or count the characters in a row, so we take them all out
+//! first.
+
+use std::borrow::Cow;
+use std::fmt::Display;
+
+/// What [`normalise`] had to change.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Changes {
+    pub byte_order_mark: bool,
+    /// The number of lines which ended with `\r\n`, along with the last line if the input ends
+    /// with a lone `\r`.
+    pub carriage_returns: usize,
+    /// The number of lines which had whitespace at the end, not counting the `\r`.
+    pub trailing_whitespace: usize,
+}
+
+impl Changes {
+    pub fn is_empty(&self) -> bool {
+        *self == Changes::default()
+    }
+}
+
+impl Display for Changes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut removed: Vec<String> = Vec::new();
+        if self.byte_order_mark {
+            removed.push("a byte order mark".to_owned());
+        }
+        if self.carriage_returns > 0 {
+            removed.push(format!(
+                "carriage returns from {} lines",
+                self.carriage_returns
+            ));
+        }
+        if self.trailing_whitespace > 0 {
+            removed.push(format!(
+                "trailing whitespace from {} lines",
+                self.trailing_whitespace
+            ));
+        }
+        if removed.is_empty() {
+            write!(f, "nothing changed")
+        } else {
+            write!(f, "removed {}", removed.join(", "))
+        }
+    }
+}
+
+/// Strip a UTF-8 byte order mark, the `\r` of CRLF line endings, and whitespace at the end of
+/// every line. The input is only copied if something had to change.
+pub fn normalise(input: &str) -> (Cow<'_, str>, Changes) {
+    let mut changes = Changes::default();
+    let body = match input.strip_prefix('\u{feff}') {
+        Some(rest) => {
+            changes.byte_order_mark = true;
+            rest
+        }
+        None => input,
+    };
+
+    // We only start copying once we find something to change, and then everything before it can
+    // be copied as it is.
+    let mut text: Option<String> = changes
+        .byte_order_mark
+        .then(|| String::with_capacity(body.len()));
+    let mut start = 0;
+    for line in body.split_inclusive('\n') {
+        let (content, newline) = match line.strip_suffix('\n') {
+            Some(content) => (content, "\n"),
+            None => (line, ""),
+        };
+        let content = match content.strip_suffix('\r') {
+            Some(content) => {
+                changes.carriage_returns += 1;
+                content
+            }
+            None => content,
+        };
+        let trimmed = content.trim_end();
+        if trimmed.len() != content.len() {
+            changes.trailing_whitespace += 1;
+        }
+        if text.is_none() && trimmed.len() + newline.len() != line.len() {
+            let mut copy = String::with_capacity(body.len());
+            copy.push_str(&body[..start]);
+            text = Some(copy);
+        }
+        if let Some(text) = &mut text {
+            text.push_str(trimmed);
+            text.push_str(newline);
+        }
+        start += line.len();
+    }
+
+    match text {
+        Some(text) => (Cow::Owned(text), changes),
+        None => (Cow::Borrowed(input), changes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalising() {
+        let (text, changes) = normalise("\u{feff}3   4\r\n4 3 \r\n\r\n  \n1 2\t");
+        assert_eq!(text, "3   4\n4 3\n\n\n1 2");
+        assert_eq!(
+            changes,
+            Changes {
+                byte_order_mark: true,
+                carriage_returns: 3,
+                trailing_whitespace: 3,
+            }
+        );
+        assert_eq!(
+            changes.to_string(),
+            "removed a byte order mark, carriage returns from 3 lines, trailing whitespace from \
+            3 lines"
+        );
+
+        let (text, changes) = normalise("3   4\n4 3\n");
+        assert!(matches!(text, Cow::Borrowed(_)));
+        assert!(changes.is_empty());
+
+        // The lines before the first change are copied as they are.
+        let (text, changes) = normalise("3   4\n4 3\n1 2\r");
+        assert_eq!(text, "3   4\n4 3\n1 2");
+        assert_eq!(changes.carriage_returns, 1);
+        assert_eq!(changes.trailing_whitespace, 0);
+    }
+}
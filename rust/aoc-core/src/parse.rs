@@ -0,0 +1,160 @@
+//! Errors for puzzle inputs which don't look the way a day expects.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// What was found where a [`ParseError`] happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Found {
+    Token(String),
+    EndOfLine,
+    EndOfInput,
+}
+
+impl Display for Found {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Found::Token(token) => write!(f, "`{token}`"),
+            Found::EndOfLine => write!(f, "the end of the line"),
+            Found::EndOfInput => write!(f, "the end of the input"),
+        }
+    }
+}
+
+/// A problem with the puzzle input, and where it is.
+///
+/// Lines and columns start from 1, and columns count characters rather than bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// A description of what should have been there, like "a location ID".
+    pub expected: String,
+    pub found: Found,
+}
+
+impl ParseError {
+    /// Make an error at a line and column which start from 0, like the ones from
+    /// [`Iterator::enumerate`].
+    pub fn new(
+        line_index: usize,
+        column_index: usize,
+        expected: impl Into<String>,
+        found: Found,
+    ) -> Self {
+        Self {
+            line: line_index + 1,
+            column: column_index + 1,
+            expected: expected.into(),
+            found,
+        }
+    }
+
+    /// Make an error about `token`, which must be a slice of `line`.
+    pub fn at_token(
+        line_index: usize,
+        line: &str,
+        token: &str,
+        expected: impl Into<String>,
+    ) -> Self {
+        let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+        debug_assert!(
+            offset + token.len() <= line.len(),
+            "the token should be part of the line"
+        );
+        Self::new(
+            line_index,
+            line[..offset].chars().count(),
+            expected,
+            Found::Token(token.to_owned()),
+        )
+    }
+
+    /// Make an error about a line which ended before we found what we expected.
+    pub fn at_end_of_line(line_index: usize, line: &str, expected: impl Into<String>) -> Self {
+        Self::new(line_index, line.chars().count(), expected, Found::EndOfLine)
+    }
+
+    /// Make an error about an input which ended before we found what we expected.
+    pub fn at_end_of_input(input: &str, expected: impl Into<String>) -> Self {
+        Self::new(input.lines().count(), 0, expected, Found::EndOfInput)
+    }
+
+    /// Render the error along with the line of the input it's on, pointing at the problem.
+    ///
+    /// ```text
+    /// line 2, column 3: expected a location ID, found `4x`
+    ///   |
+    /// 2 | 3 4x
+    ///   |   ^^
+    /// ```
+    pub fn diagnostic(&self, input: &str) -> String {
+        let mut output = self.to_string();
+        let Some(line) = input.lines().nth(self.line - 1) else {
+            return output;
+        };
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let underline = match &self.found {
+            Found::Token(token) => "^".repeat(token.chars().count().max(1)),
+            Found::EndOfLine | Found::EndOfInput => "^".to_owned(),
+        };
+        output.push_str(&format!("\n{gutter} |\n{number} | {line}\n{gutter} | "));
+        output.push_str(&" ".repeat(self.column - 1));
+        output.push_str(&underline);
+        output
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse `token`, which must be a slice of `line`, returning an error pointing at it if it isn't
+/// valid.
+pub fn parse_token<T: FromStr>(
+    line_index: usize,
+    line: &str,
+    token: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at_token(line_index, line, token, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_of_token() {
+        let line = "12  4x 5";
+        let token = line.split_whitespace().nth(1).unwrap();
+        let error = parse_token::<usize>(1, line, token, "a number").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.found, Found::Token("4x".to_owned()));
+    }
+
+    #[test]
+    fn diagnostic() {
+        let input = "1 2\n3 4x\n";
+        let line = input.lines().nth(1).unwrap();
+        let error = ParseError::at_token(1, line, &line[2..], "a location ID");
+        assert_eq!(
+            error.diagnostic(input),
+            "line 2, column 3: expected a location ID, found `4x`
+  |
+2 | 3 4x
+  |   ^^"
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! A rectangular grid for the puzzles which come as a picture of characters.
+//!
+//! Adapted from my own 'wordsearcher' program. The cells are stored row by row in one [`Vec`], so
+//! every cell has both an index into that vec and a coordinate `[x, y]`, where `[0, 0]` is the top
+//! left and `y` grows downwards.
+
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use aoc_core::ParseError;
+
+pub type Coord = [usize; 2];
+pub type Vector = [isize; 2];
+
+/// Up, right, down and left.
+pub const FOUR_DIRECTIONS: [Vector; 4] = [[0, -1], [1, 0], [0, 1], [-1, 0]];
+
+/// Every direction, including the diagonals, going clockwise from right, since `y` grows
+/// downwards.
+pub const EIGHT_DIRECTIONS: [Vector; 8] = [
+    [1, 0],
+    [1, 1],
+    [0, 1],
+    [-1, 1],
+    [-1, 0],
+    [-1, -1],
+    [0, -1],
+    [1, -1],
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Make a grid from its rows. Every row should have the same length.
+    pub fn from_2d(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        let cells: Vec<T> = rows.into_iter().flatten().collect();
+        debug_assert_eq!(
+            cells.len(),
+            width * height,
+            "every row should have the same length"
+        );
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The number of cells in the grid.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Get the coordinate representation of an index.
+    pub fn get_coord(&self, index: usize) -> Coord {
+        [index % self.width, index / self.width]
+    }
+
+    /// Get the index which a coordinate represents.
+    pub fn get_index(&self, [x, y]: Coord) -> usize {
+        x + self.width * y
+    }
+
+    /// Check if a coordinate is in bounds.
+    pub fn in_bounds(&self, [a, b]: Vector) -> bool {
+        a >= 0 && b >= 0 && a < self.width as isize && b < self.height as isize
+    }
+
+    /// Adds a vector to an index, returning [`Option::None`] if the vector takes the index
+    /// out-of-bounds.
+    pub fn index_plus_vector(&self, index: usize, vector: Vector) -> Option<usize> {
+        let [x, y] = self.get_coord(index);
+        let new_coord = [x as isize + vector[0], y as isize + vector[1]];
+        if self.in_bounds(new_coord) {
+            Some(self.get_index(new_coord.map(|i| i as usize)))
+        } else {
+            None
+        }
+    }
+
+    /// Get the cell at a coordinate, returning [`Option::None`] if it's out-of-bounds.
+    pub fn get(&self, [x, y]: Coord) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[self.get_index([x, y])])
+        } else {
+            None
+        }
+    }
+
+    /// Find the index of the first cell (reading row by row) which is equal to `to_find`.
+    pub fn find_index_of(&self, to_find: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.cells.iter().position(|cell| cell == to_find)
+    }
+
+    /// All the cells, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Iterate over the rows, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, which we'd have for an empty grid.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterate over the columns, from left to right. Each column is an iterator over its cells
+    /// from top to bottom.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| (0..self.height).map(move |y| &self.cells[x + self.width * y]))
+    }
+
+    /// Iterate over the indices of the cells next to an index, including the diagonals.
+    pub fn neighbours(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        EIGHT_DIRECTIONS
+            .into_iter()
+            .filter_map(move |vector| self.index_plus_vector(index, vector))
+    }
+
+    /// Iterate over the indices of the cells directly above, to the right of, below and to the left
+    /// of an index.
+    pub fn orthogonal_neighbours(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        FOUR_DIRECTIONS
+            .into_iter()
+            .filter_map(move |vector| self.index_plus_vector(index, vector))
+    }
+}
+
+impl Grid<char> {
+    /// Parse a grid of characters, one row per line, checking that every row is as wide as the
+    /// first.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let rows: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let width = rows.first().map_or(0, Vec::len);
+        for (i, (line, row)) in input.lines().zip(&rows).enumerate() {
+            let expected = || format!("a row of {width} characters");
+            if row.len() < width {
+                return Err(ParseError::at_end_of_line(i, line, expected()));
+            }
+            if row.len() > width {
+                let (offset, _) = line
+                    .char_indices()
+                    .nth(width)
+                    .expect("The row is too long.");
+                return Err(ParseError::at_token(i, line, &line[offset..], expected()));
+            }
+        }
+        Ok(Self::from_2d(rows))
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.cells[index]
+    }
+}
+
+impl<T> IntoIterator for Grid<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.into_iter()
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::from_2d(["abc", "def"].map(|row| row.chars().collect()).to_vec())
+    }
+
+    #[test]
+    fn coords_and_indices() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get_coord(4), [1, 1]);
+        assert_eq!(grid.get_index([1, 1]), 4);
+        assert_eq!(grid.get([2, 1]), Some(&'f'));
+        assert_eq!(grid.get([3, 0]), None);
+    }
+
+    #[test]
+    fn plus_vector() {
+        let grid = sample();
+        assert_eq!(grid.index_plus_vector(0, [1, 1]), Some(4));
+        assert_eq!(grid.index_plus_vector(0, [-1, 0]), None);
+        assert_eq!(grid.index_plus_vector(2, [1, 0]), None);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = sample();
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, ["abc", "def"]);
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn parse_ragged() {
+        let error = Grid::parse("abc\nde\nfghi\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        let error = Grid::parse("abc\nfghi\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(Grid::parse("abc\ndef").unwrap(), sample());
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = sample();
+        let mut neighbours: Vec<usize> = grid.neighbours(0).collect();
+        neighbours.sort();
+        assert_eq!(neighbours, [1, 3, 4]);
+        let mut orthogonal: Vec<usize> = grid.orthogonal_neighbours(4).collect();
+        orthogonal.sort();
+        assert_eq!(orthogonal, [1, 3, 5]);
+    }
+}
@@ -0,0 +1,130 @@
+//! The machine-readable output of `aoc run --format json`.
+//!
+//! Every part that was run becomes one [`Record`], and all of them are printed as one JSON array,
+//! so scripts don't have to scrape the `Part 1: ...` lines.
+
+use std::path::Path;
+
+use aoc_core::Answer;
+use serde::{Serialize, Serializer};
+
+use crate::registry::Run;
+
+/// The answer to one part of one day, on one input.
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub day: u8,
+    pub part: u8,
+    /// A number if the answer is one, otherwise a string, or `null` if the part failed.
+    #[serde(serialize_with = "serialize_answer")]
+    pub answer: Option<&'a Answer>,
+    /// Why the part failed, which is left out if it didn't.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ns: u64,
+    /// Where the input was read from, or `null` if it came from stdin.
+    pub input_path: Option<&'a Path>,
+}
+
+impl<'a> Record<'a> {
+    /// Make a record for every part in a run.
+    pub fn from_run(day: u8, run: &'a Run, input_path: Option<&'a Path>) -> Vec<Self> {
+        run.parts
+            .iter()
+            .map(|part| Record {
+                day,
+                part: part.part.into(),
+                answer: part.answer.as_ref().ok(),
+                error: part.answer.as_ref().err().map(ToString::to_string),
+                // No part is going to take 584 years.
+                duration_ns: part.measurement.duration.as_nanos() as u64,
+                input_path,
+            })
+            .collect()
+    }
+}
+
+fn serialize_answer<S: Serializer>(
+    answer: &Option<&Answer>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match answer {
+        Some(Answer::Integer(n)) => serializer.serialize_i64(*n),
+        Some(Answer::BigInteger(n)) => serializer.serialize_i128(*n),
+        Some(Answer::Text(text)) => serializer.serialize_str(text),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc_core::{Part, SolveError};
+    use serde_json::json;
+
+    use super::*;
+    use crate::registry::PartRun;
+    use aoc_core::measure::Measurement;
+
+    #[test]
+    fn records() {
+        let measurement = Measurement {
+            duration: Duration::from_nanos(1500),
+            peak_heap: 0,
+        };
+        let run = Run {
+            parse: measurement,
+            parts: vec![
+                PartRun {
+                    part: Part::One,
+                    answer: Ok(Answer::from(11)),
+                    measurement,
+                },
+                PartRun {
+                    part: Part::Two,
+                    answer: Ok(Answer::Text("ABC".to_owned())),
+                    measurement,
+                },
+                PartRun {
+                    part: Part::Two,
+                    answer: Err(SolveError("the answer is too big".to_owned())),
+                    measurement,
+                },
+            ],
+        };
+        let records = Record::from_run(1, &run, Some(Path::new("inputs/day01.txt")));
+        assert_eq!(
+            serde_json::to_value(&records).unwrap(),
+            json!([
+                {
+                    "day": 1,
+                    "part": 1,
+                    "answer": 11,
+                    "duration_ns": 1500,
+                    "input_path": "inputs/day01.txt",
+                },
+                {
+                    "day": 1,
+                    "part": 2,
+                    "answer": "ABC",
+                    "duration_ns": 1500,
+                    "input_path": "inputs/day01.txt",
+                },
+                {
+                    "day": 1,
+                    "part": 2,
+                    "answer": null,
+                    "error": "the answer is too big",
+                    "duration_ns": 1500,
+                    "input_path": "inputs/day01.txt",
+                },
+            ])
+        );
+        let stdin = Record::from_run(1, &run, None);
+        assert_eq!(
+            serde_json::to_value(&stdin[0]).unwrap()["input_path"],
+            json!(null)
+        );
+    }
+}
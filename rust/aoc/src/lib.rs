@@ -0,0 +1,6 @@
+//! The tooling around the solutions, shared by the `aoc` binary.
+
+pub mod json;
+pub mod registry;
+pub mod table;
+pub mod verify;
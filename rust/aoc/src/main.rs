@@ -0,0 +1,387 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc::json::Record;
+use aoc::registry::{self, Run, Solver};
+use aoc::table::Table;
+use aoc::verify::{self, Outcome};
+use aoc_core::input::{self, InputArgs, InputError, Source};
+use aoc_core::measure::{CountingAllocator, Measurement};
+use aoc_core::{normalise, Part};
+use clap::{Parser, Subcommand, ValueEnum};
+
+// We count every allocation so that `--mem` can report the peak heap usage.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs the Advent of Code 2024 solutions.
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a day's solution and print the answers.
+    Run {
+        /// The day to run. If this is left out, every day with an input in `inputs/` is run, and
+        /// the results are shown in a table.
+        day: Option<u8>,
+        /// Only run this part (1 or 2). Both parts are run if this is left out.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Report how long parsing and each part took.
+        #[arg(long)]
+        time: bool,
+        /// Report the most heap memory in use at once while parsing and during each part.
+        #[arg(long)]
+        mem: bool,
+        /// How to print the answers.
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// List the days which have a solution.
+    List,
+    /// Check every day against the answers we already know are correct.
+    Verify {
+        /// The file with the known answers.
+        #[arg(long, default_value = "answers.toml")]
+        answers: PathBuf,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// `Part 1: ...` lines for one day, or a table for every day.
+    Text,
+    /// A JSON array with an object for each part, which always includes how long it took.
+    Json,
+}
+
+/// What to run and what to report.
+struct RunOptions {
+    parts: Vec<Part>,
+    time: bool,
+    mem: bool,
+    format: Format,
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Run {
+            day,
+            part,
+            input,
+            time,
+            mem,
+            format,
+        } => {
+            let parts = match part {
+                Some(1) => vec![Part::One],
+                Some(_) => vec![Part::Two],
+                None => Part::BOTH.to_vec(),
+            };
+            let options = RunOptions {
+                parts,
+                time,
+                mem,
+                format,
+            };
+            match day {
+                Some(day) => run_day(day, &input, &options),
+                None if input.inputs.is_empty() && input.files.is_empty() => run_all(&options),
+                None => {
+                    eprintln!("error: input files can only be given along with a day");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::List => {
+            for solver in registry::solvers() {
+                println!("Day {:02}", solver.day());
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Verify { answers } => run_verify(&answers),
+    }
+}
+
+/// Read an input and run a solver on it, printing what went wrong if either fails.
+///
+/// A run where some of the parts failed is still returned, so that the parts which did work can
+/// be shown, but `failed` is set.
+fn run_source(
+    solver: &Solver,
+    source: &Source,
+    options: &RunOptions,
+    failed: &mut bool,
+) -> Option<Run> {
+    let input = match source.read() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: {e}");
+            *failed = true;
+            return None;
+        }
+    };
+    let (input, changes) = normalise(&input);
+    if !changes.is_empty() {
+        eprintln!("warning: {source}: {changes}");
+    }
+    match solver.run(&input, &options.parts) {
+        Ok(run) => {
+            for part in &run.parts {
+                if let Err(e) = &part.answer {
+                    eprintln!(
+                        "error: day {:02}, {source}: part {}: {e}",
+                        solver.day(),
+                        part.part
+                    );
+                    *failed = true;
+                }
+            }
+            Some(run)
+        }
+        Err(e) => {
+            eprintln!(
+                "error: day {:02}, {source}: {}",
+                solver.day(),
+                e.diagnostic(&input)
+            );
+            *failed = true;
+            None
+        }
+    }
+}
+
+fn run_day(day: u8, input: &InputArgs, options: &RunOptions) -> ExitCode {
+    let Some(solver) = registry::get(day) else {
+        eprintln!("error: there is no solution for day {day}");
+        return ExitCode::FAILURE;
+    };
+    let sources = match input.sources(day) {
+        Ok(sources) => sources,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut records: Vec<(Source, Run)> = Vec::new();
+    let mut failed = false;
+    for source in sources {
+        if let Some(run) = run_source(solver, &source, options, &mut failed) {
+            records.push((source, run));
+        }
+    }
+
+    match options.format {
+        Format::Text => {
+            for (i, (source, run)) in records.iter().enumerate() {
+                // We only need to say which input is which if there's more than one.
+                if records.len() > 1 || failed {
+                    if i > 0 {
+                        println!();
+                    }
+                    println!("{source}:");
+                }
+                print_run(run, options);
+            }
+        }
+        Format::Json => {
+            let json: Vec<Record> = records
+                .iter()
+                .flat_map(|(source, run)| Record::from_run(day, run, source.path()))
+                .collect();
+            print_json(&json);
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn print_run(run: &Run, options: &RunOptions) {
+    if let Some(description) = describe(&run.parse, options) {
+        println!("Parse: {description}");
+    }
+    for part in &run.parts {
+        // A part which failed has already said why.
+        let Ok(answer) = &part.answer else {
+            continue;
+        };
+        match describe(&part.measurement, options) {
+            Some(description) => println!("Part {}: {answer} ({description})", part.part),
+            None => println!("Part {}: {answer}", part.part),
+        }
+    }
+}
+
+fn run_all(options: &RunOptions) -> ExitCode {
+    let mut runs: Vec<(u8, Source, Run)> = Vec::new();
+    let mut failed = false;
+    for solver in registry::solvers() {
+        let sources = match input::default_sources(Path::new(input::INPUTS_DIR), solver.day()) {
+            Ok(sources) => sources,
+            Err(InputError::NoInput { .. }) => {
+                eprintln!("skipping day {:02}: there's no input", solver.day());
+                continue;
+            }
+            Err(e) => {
+                eprintln!("error: {e}");
+                failed = true;
+                continue;
+            }
+        };
+        for source in sources {
+            if let Some(run) = run_source(solver, &source, options, &mut failed) {
+                runs.push((solver.day(), source, run));
+            }
+        }
+    }
+
+    match options.format {
+        Format::Text => print_table(&runs, options),
+        Format::Json => {
+            let records: Vec<Record> = runs
+                .iter()
+                .flat_map(|(day, source, run)| Record::from_run(*day, run, source.path()))
+                .collect();
+            print_json(&records);
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn print_table(runs: &[(u8, Source, Run)], options: &RunOptions) {
+    let mut headers: Vec<String> = vec!["Day".to_owned(), "Input".to_owned()];
+    headers.extend(options.parts.iter().map(|part| format!("Part {part}")));
+    if options.time {
+        headers.push("Parse time".to_owned());
+        headers.extend(options.parts.iter().map(|part| format!("Part {part} time")));
+    }
+    if options.mem {
+        headers.push("Parse heap".to_owned());
+        headers.extend(options.parts.iter().map(|part| format!("Part {part} heap")));
+    }
+    let mut table = Table::new(headers);
+
+    let mut total = Duration::ZERO;
+    for (day, source, run) in runs {
+        let mut row: Vec<String> = vec![format!("{day:02}"), source.to_string()];
+        row.extend(run.parts.iter().map(|part| match &part.answer {
+            Ok(answer) => answer.to_string(),
+            Err(_) => "error".to_owned(),
+        }));
+        if options.time {
+            row.push(format_duration(run.parse.duration));
+            row.extend(
+                run.parts
+                    .iter()
+                    .map(|part| format_duration(part.measurement.duration)),
+            );
+        }
+        if options.mem {
+            row.push(format_bytes(run.parse.peak_heap));
+            row.extend(
+                run.parts
+                    .iter()
+                    .map(|part| format_bytes(part.measurement.peak_heap)),
+            );
+        }
+        table.push_row(row);
+        total += run.parse.duration;
+        total += run
+            .parts
+            .iter()
+            .map(|part| part.measurement.duration)
+            .sum::<Duration>();
+    }
+
+    print!("{table}");
+    if options.time {
+        println!("\nTotal time: {}", format_duration(total));
+    }
+}
+
+fn print_json(records: &[Record]) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(records).expect("Records are always valid JSON.")
+    );
+}
+
+/// Describe a measurement with whichever readings were asked for.
+fn describe(measurement: &Measurement, options: &RunOptions) -> Option<String> {
+    let mut readings: Vec<String> = Vec::new();
+    if options.time {
+        readings.push(format_duration(measurement.duration));
+    }
+    if options.mem {
+        readings.push(format!("{} peak heap", format_bytes(measurement.peak_heap)));
+    }
+    (!readings.is_empty()).then(|| readings.join(", "))
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{duration:.2?}")
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+fn run_verify(answers: &Path) -> ExitCode {
+    let checks = match verify::verify(answers) {
+        Ok(checks) => checks,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for check in &checks {
+        let input = check
+            .input
+            .as_ref()
+            .map_or("-".to_owned(), |path| path.display().to_string());
+        println!(
+            "Day {:02} part {}  {input:<24}  {}",
+            check.day, check.part, check.outcome
+        );
+        match check.outcome {
+            Outcome::Pass => passed += 1,
+            Outcome::Fail { .. } | Outcome::Error(_) => failed += 1,
+            Outcome::Missing(_) => missing += 1,
+        }
+    }
+    println!("\n{passed} passed, {failed} failed, {missing} missing");
+
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,105 @@
+//! The list of every day we have a solution for.
+
+use aoc_core::measure::{measure, Measurement};
+use aoc_core::{Answer, ParseError, Part, Solution, SolveError};
+use day01::Day01;
+use day02::Day02;
+use day03::Day03;
+use day04::Day04;
+use day05::Day05;
+use day06::Day06;
+
+/// The answer to one part, and how getting it went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartRun {
+    pub part: Part,
+    /// The answer, or why there isn't one. A part failing doesn't stop the others from running.
+    pub answer: Result<Answer, SolveError>,
+    pub measurement: Measurement,
+}
+
+/// Everything that happened while running a solution on one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub parse: Measurement,
+    /// The parts in the order they were asked for.
+    pub parts: Vec<PartRun>,
+}
+
+/// A [`Solution`] with its types erased, so that every day can be stored in one list.
+#[derive(Clone, Copy)]
+pub struct Solver {
+    day: u8,
+    run: fn(&str, &[Part]) -> Result<Run, ParseError>,
+}
+
+impl Solver {
+    const fn new<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            run: run::<S>,
+        }
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Parse the input once and then run each of the given parts on it, measuring every step.
+    pub fn run(&self, input: &str, parts: &[Part]) -> Result<Run, ParseError> {
+        (self.run)(input, parts)
+    }
+}
+
+fn run<S: Solution>(input: &str, parts: &[Part]) -> Result<Run, ParseError> {
+    let (parsed, parse) = measure(|| S::parse(input));
+    let parsed = parsed?;
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let (answer, measurement) = measure(|| S::solve(&parsed, part));
+            PartRun {
+                part,
+                answer,
+                measurement,
+            }
+        })
+        .collect();
+    Ok(Run { parse, parts })
+}
+
+/// Every solver, sorted by day.
+const SOLVERS: &[Solver] = &[
+    Solver::new::<Day01>(),
+    Solver::new::<Day02>(),
+    Solver::new::<Day03>(),
+    Solver::new::<Day04>(),
+    Solver::new::<Day05>(),
+    Solver::new::<Day06>(),
+];
+
+/// All the solvers we have, sorted by day.
+pub fn solvers() -> &'static [Solver] {
+    SOLVERS
+}
+
+/// Look up the solver for a day, returning [`Option::None`] if we haven't solved it.
+pub fn get(day: u8) -> Option<&'static Solver> {
+    SOLVERS.iter().find(|solver| solver.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solvers_sorted_and_unique() {
+        assert!(solvers().windows(2).all(|w| w[0].day() < w[1].day()));
+    }
+
+    #[test]
+    fn get_day() {
+        assert_eq!(get(6).map(Solver::day), Some(6));
+        assert!(get(25).is_none());
+    }
+}
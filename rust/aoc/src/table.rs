@@ -0,0 +1,92 @@
+//! Plain text tables for printing results.
+
+use std::fmt::Display;
+
+/// A table which is printed with every column lined up, and with numbers aligned to the right.
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            headers: headers.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add a row, which should have a cell for every header.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = impl Into<String>>) {
+        let row: Vec<String> = row.into_iter().map(Into::into).collect();
+        debug_assert_eq!(
+            row.len(),
+            self.headers.len(),
+            "every row needs a cell for each header"
+        );
+        self.rows.push(row);
+    }
+}
+
+/// Whether a cell looks like a number, so should be aligned to the right.
+fn is_numeric(cell: &str) -> bool {
+    cell.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|i| {
+                std::iter::once(&self.headers[i])
+                    .chain(self.rows.iter().map(|row| &row[i]))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let write_row = |f: &mut std::fmt::Formatter<'_>, row: &[String]| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| {
+                    if is_numeric(cell) {
+                        format!("{cell:>width$}")
+                    } else {
+                        format!("{cell:<width$}")
+                    }
+                })
+                .collect();
+            writeln!(f, "{}", cells.join("  ").trim_end())
+        };
+
+        write_row(f, &self.headers)?;
+        let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+        writeln!(f, "{}", rule.join("  "))?;
+        for row in &self.rows {
+            write_row(f, row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aligned() {
+        let mut table = Table::new(["Day", "Answer"]);
+        table.push_row(["01", "11"]);
+        table.push_row(["02", "1234"]);
+        table.push_row(["03", "text"]);
+        assert_eq!(
+            table.to_string(),
+            "Day  Answer
+---  ------
+ 01      11
+ 02    1234
+ 03  text
+"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Checking every solution against the answers we already know are correct.
+//!
+//! The known answers live in an `answers.toml` file, which has one `[[answer]]` table for each
+//! (day, part, input file). See the one at the root of the workspace for an example.
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use aoc_core::{Answer, Part};
+use serde::Deserialize;
+
+use crate::registry;
+
+#[derive(Deserialize)]
+struct AnswersFile {
+    #[serde(default)]
+    answer: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    day: u8,
+    part: u8,
+    input: PathBuf,
+    answer: ExpectedAnswer,
+}
+
+/// A known answer as it's written in the file, which can be either an integer or a string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ExpectedAnswer {
+    Integer(i64),
+    Text(String),
+}
+
+impl From<ExpectedAnswer> for Answer {
+    fn from(expected: ExpectedAnswer) -> Self {
+        match expected {
+            ExpectedAnswer::Integer(n) => Answer::from(n),
+            // Integers too big for TOML have to be written as strings.
+            ExpectedAnswer::Text(text) => match text.parse::<i128>() {
+                Ok(n) => Answer::from(n),
+                Err(_) => Answer::Text(text),
+            },
+        }
+    }
+}
+
+/// What happened when we checked one part against one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: Answer,
+        got: Answer,
+    },
+    /// The solution couldn't even get an answer, for example because the input doesn't parse.
+    Error(String),
+    /// We couldn't check this, because the input file, the solution or the known answer is
+    /// missing.
+    Missing(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Fail { expected, got } => write!(f, "FAIL (expected {expected}, got {got})"),
+            Outcome::Error(e) => write!(f, "FAIL ({e})"),
+            Outcome::Missing(reason) => write!(f, "missing ({reason})"),
+        }
+    }
+}
+
+/// The result of checking one part of one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    /// The input file, as it's written in the answers file.
+    pub input: Option<PathBuf>,
+    pub outcome: Outcome,
+}
+
+/// A problem with the answers file itself.
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(PathBuf, std::io::Error),
+    Toml(PathBuf, toml::de::Error),
+    InvalidPart(PathBuf, u8),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::Io(path, e) => write!(f, "could not read {}: {e}", path.display()),
+            AnswersError::Toml(path, e) => write!(f, "could not parse {}: {e}", path.display()),
+            AnswersError::InvalidPart(path, part) => {
+                write!(
+                    f,
+                    "{} has an answer for part {part}, which doesn't exist",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+/// Check every known answer in the file at `answers_path`, and also report every part of every
+/// registered day which doesn't have a known answer at all.
+///
+/// The checks are sorted by day and then by part.
+pub fn verify(answers_path: &Path) -> Result<Vec<Check>, AnswersError> {
+    let text = std::fs::read_to_string(answers_path)
+        .map_err(|e| AnswersError::Io(answers_path.to_owned(), e))?;
+    let file: AnswersFile =
+        toml::from_str(&text).map_err(|e| AnswersError::Toml(answers_path.to_owned(), e))?;
+    let base = answers_path.parent().unwrap_or(Path::new("."));
+
+    let mut checks: Vec<Check> = Vec::new();
+    for entry in file.answer {
+        let part = Part::try_from(entry.part)
+            .map_err(|part| AnswersError::InvalidPart(answers_path.to_owned(), part))?;
+        let outcome = check(
+            entry.day,
+            part,
+            &base.join(&entry.input),
+            entry.answer.into(),
+        );
+        checks.push(Check {
+            day: entry.day,
+            part,
+            input: Some(entry.input),
+            outcome,
+        });
+    }
+
+    for solver in registry::solvers() {
+        for part in Part::BOTH {
+            if !checks
+                .iter()
+                .any(|c| c.day == solver.day() && c.part == part)
+            {
+                checks.push(Check {
+                    day: solver.day(),
+                    part,
+                    input: None,
+                    outcome: Outcome::Missing("no known answer".to_owned()),
+                });
+            }
+        }
+    }
+
+    // A stable sort keeps the entries for the same part in the order they were written.
+    checks.sort_by_key(|c| (c.day, c.part));
+    Ok(checks)
+}
+
+fn check(day: u8, part: Part, input_path: &Path, expected: Answer) -> Outcome {
+    let Some(solver) = registry::get(day) else {
+        return Outcome::Missing("no solution".to_owned());
+    };
+    let input = match std::fs::read_to_string(input_path) {
+        Ok(input) => input,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Outcome::Missing("no input file".to_owned());
+        }
+        Err(e) => return Outcome::Error(format!("could not read the input: {e}")),
+    };
+    let (input, _) = aoc_core::normalise(&input);
+    match solver.run(&input, &[part]) {
+        Ok(mut run) => match run.parts.remove(0).answer {
+            Ok(got) if got == expected => Outcome::Pass,
+            Ok(got) => Outcome::Fail { expected, got },
+            Err(e) => Outcome::Error(e.to_string()),
+        },
+        Err(e) => Outcome::Error(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_answers() {
+        let answers = Path::new(env!("CARGO_MANIFEST_DIR")).join("../answers.toml");
+        let checks = verify(&answers).unwrap();
+        assert!(!checks.is_empty());
+        for check in checks {
+            assert_eq!(
+                check.outcome,
+                Outcome::Pass,
+                "day {} part {}",
+                check.day,
+                check.part
+            );
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use aoc_core::bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day01::Day01;
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
+const SAMPLE: &str = include_str!("../../samples/day01.txt");
+
+/// Two lists of `lines` five digit location IDs. The real input has a thousand lines.
+fn generated_input(lines: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut input = String::new();
+    for _ in 0..lines {
+        let left: u32 = rng.random_range(10_000..100_000);
+        // Make some of the IDs match so that part 2 has something to count.
+        let right: u32 = if rng.random_bool(0.3) {
+            left
+        } else {
+            rng.random_range(10_000..100_000)
+        };
+        input.push_str(&format!("{left}   {right}\n"));
+    }
+    input
+}
+
+fn bench(c: &mut Criterion) {
+    bench_solution::<Day01>(c, "sample", SAMPLE);
+    bench_solution::<Day01>(c, "generated", &generated_input(10_000));
+    // Too slow to run before part 2 stopped being quadratic, so it has no baseline.
+    bench_solution::<Day01>(c, "million", &generated_input(1_000_000));
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
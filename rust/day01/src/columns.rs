@@ -0,0 +1,260 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use aoc_core::{parse_token, ParseError};
+
+use crate::lists::similarity_score;
+use crate::matrix::PairMatrix;
+use crate::metric::DistanceMetric;
+use crate::Overflow;
+
+/// Any number of lists of location IDs, written side by side in columns, for when more than two
+/// groups of historians have made a list.
+///
+/// Like [`LocationLists`](crate::LocationLists), every list is kept sorted. Unlike it, the lists
+/// can be different lengths, although then only [`DistanceMetric::OptimalAssignment`] can
+/// measure the distance between them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocationColumns {
+    columns: Vec<Vec<usize>>,
+}
+
+impl LocationColumns {
+    /// Make the lists from their IDs, in any order.
+    pub fn new(mut columns: Vec<Vec<usize>>) -> Self {
+        for column in &mut columns {
+            column.sort_unstable();
+        }
+        Self { columns }
+    }
+
+    /// Every list, each from smallest to largest.
+    pub fn columns(&self) -> &[Vec<usize>] {
+        &self.columns
+    }
+
+    /// The number of lists.
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// The distance between every pair of lists. This is symmetric, and zero along the diagonal.
+    pub fn distance_matrix(&self, metric: DistanceMetric) -> Result<PairMatrix, MatrixError> {
+        self.matrix(|[i, j]| {
+            let (left, right) = (&self.columns[i], &self.columns[j]);
+            match metric.distance(left, right) {
+                Some(distance) => Ok(distance?),
+                None => Err(MatrixError::Lengths {
+                    metric,
+                    lists: [i + 1, j + 1],
+                    lengths: [left.len(), right.len()],
+                }),
+            }
+        })
+    }
+
+    /// The similarity score of every pair of lists, where the row is the list on the left and the
+    /// column is the one on the right. Unlike the distance, this isn't symmetric.
+    pub fn similarity_matrix(&self) -> Result<PairMatrix, MatrixError> {
+        self.matrix(|[i, j]| Ok(similarity_score(&self.columns[i], &self.columns[j])?))
+    }
+
+    /// Work out `f` for the index of every pair of lists.
+    fn matrix(
+        &self,
+        f: impl Fn([usize; 2]) -> Result<usize, MatrixError>,
+    ) -> Result<PairMatrix, MatrixError> {
+        let width = self.width();
+        let rows = (0..width)
+            .map(|i| (0..width).map(|j| f([i, j])).collect())
+            .collect::<Result<_, _>>()?;
+        Ok(PairMatrix::new(rows))
+    }
+}
+
+/// Why a [`PairMatrix`] couldn't be worked out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatrixError {
+    Overflow(Overflow),
+    /// Two of the lists, numbered from 1, are different lengths, which the metric can't handle.
+    Lengths {
+        metric: DistanceMetric,
+        lists: [usize; 2],
+        lengths: [usize; 2],
+    },
+}
+
+impl Display for MatrixError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatrixError::Overflow(e) => write!(f, "{e}"),
+            MatrixError::Lengths {
+                metric,
+                lists: [a, b],
+                lengths: [a_len, b_len],
+            } => write!(
+                f,
+                "lists {a} and {b} have {a_len} and {b_len} IDs, and the {metric} distance can \
+                only compare lists of the same length (the optimal assignment can compare any)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MatrixError {}
+
+impl From<Overflow> for MatrixError {
+    fn from(e: Overflow) -> Self {
+        MatrixError::Overflow(e)
+    }
+}
+
+impl FromStr for LocationColumns {
+    type Err = ParseError;
+
+    /// Parse the lists from columns of IDs separated by whitespace. Every line has to have as many
+    /// IDs as the first, but a `-` can stand in for an ID, for lists which are shorter than others.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let columns: Vec<Vec<Option<usize>>> =
+            parse_cells(input, None, |i, line, token| match token {
+                "-" => Ok(None),
+                _ => parse_token(i, line, token, "a location ID or `-`").map(Some),
+            })?;
+        let columns = columns
+            .into_iter()
+            .map(|column| column.into_iter().flatten().collect())
+            .collect();
+        Ok(Self::new(columns))
+    }
+}
+
+/// Parse columns of IDs, transposing them into one [`Vec`] per column. If `width` isn't given, the
+/// first line decides it.
+pub(crate) fn parse_columns<T: FromStr + Clone>(
+    input: &str,
+    width: Option<usize>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    parse_cells(input, width, |i, line, token| {
+        parse_token(i, line, token, "a location ID")
+    })
+}
+
+/// Like [`parse_columns`], but with each whitespace-separated token on the `i`th line parsed by
+/// `parse_cell(i, line, token)`.
+fn parse_cells<T: Clone>(
+    input: &str,
+    width: Option<usize>,
+    parse_cell: impl Fn(usize, &str, &str) -> Result<T, ParseError>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let mut columns: Vec<Vec<T>> = vec![Vec::new(); width.unwrap_or(0)];
+    for (i, line) in input.lines().enumerate() {
+        // After the first line, every line needs as many IDs as the first.
+        let expected_width = if i == 0 { width } else { Some(columns.len()) };
+        let ids = parse_cells_of_line(i, line, expected_width, &parse_cell)?;
+        if i == 0 && width.is_none() {
+            columns = vec![Vec::new(); ids.len()];
+        }
+        for (column, id) in columns.iter_mut().zip(ids) {
+            column.push(id);
+        }
+    }
+    Ok(columns)
+}
+
+/// Parse the IDs on the `i`th line, checking that there are `width` of them if that's given, or
+/// at least one if not.
+pub(crate) fn parse_line<T: FromStr>(
+    i: usize,
+    line: &str,
+    width: Option<usize>,
+) -> Result<Vec<T>, ParseError> {
+    parse_cells_of_line(i, line, width, |i, line, token| {
+        parse_token(i, line, token, "a location ID")
+    })
+}
+
+fn parse_cells_of_line<T>(
+    i: usize,
+    line: &str,
+    width: Option<usize>,
+    parse_cell: impl Fn(usize, &str, &str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    let ids: Vec<T> = line
+        .split_whitespace()
+        .map(|token| parse_cell(i, line, token))
+        .collect::<Result<_, _>>()?;
+    let width = width.unwrap_or(ids.len()).max(1);
+    if ids.len() < width {
+        let expected = match width {
+            1 => "a location ID".to_owned(),
+            _ => format!("{width} location IDs"),
+        };
+        return Err(ParseError::at_end_of_line(i, line, expected));
+    }
+    if ids.len() > width {
+        let extra = line
+            .split_whitespace()
+            .nth(width)
+            .expect("We counted them.");
+        return Err(ParseError::at_token(i, line, extra, "the end of the line"));
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn three_columns() {
+        let columns: LocationColumns = "3 4 3\n4 3 9\n2 5 1\n".parse().unwrap();
+        assert_eq!(columns.width(), 3);
+        assert_eq!(
+            columns
+                .distance_matrix(DistanceMetric::Absolute)
+                .unwrap()
+                .rows(),
+            [vec![0, 3, 6], vec![3, 0, 7], vec![6, 7, 0]]
+        );
+        assert_eq!(
+            columns.similarity_matrix().unwrap().rows(),
+            [vec![9, 7, 3], vec![7, 12, 3], vec![3, 3, 13]]
+        );
+        let error = "3 4 3\n4 3\n".parse::<LocationColumns>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+    }
+
+    #[test]
+    fn different_lengths() {
+        let columns: LocationColumns = "1 5 1\n9 - 5\n5 - -\n".parse().unwrap();
+        assert_eq!(columns.columns(), [vec![1, 5, 9], vec![5], vec![1, 5]]);
+        assert_eq!(
+            columns
+                .distance_matrix(DistanceMetric::OptimalAssignment)
+                .unwrap()
+                .rows(),
+            [vec![0, 0, 0], vec![0, 0, 0], vec![0, 0, 0]]
+        );
+        let error = columns
+            .distance_matrix(DistanceMetric::Absolute)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            MatrixError::Lengths {
+                metric: DistanceMetric::Absolute,
+                lists: [1, 2],
+                lengths: [3, 1],
+            }
+        );
+        assert_eq!(
+            columns.similarity_matrix().unwrap().rows(),
+            [vec![15, 5, 6], vec![5, 5, 5], vec![6, 5, 6]]
+        );
+
+        let columns: LocationColumns = "1 2\n8 -\n".parse().unwrap();
+        let optimal = columns.distance_matrix(DistanceMetric::OptimalAssignment);
+        assert_eq!(optimal.unwrap().rows(), [vec![0, 1], vec![1, 0]]);
+        let error = "1 2\nx -\n".parse::<LocationColumns>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt::Display;
+
+use crate::{LocationLists, Overflow};
+
+/// How much one ID on the left adds to the similarity score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contribution {
+    pub id: usize,
+    /// How many times the ID is in the left list.
+    pub left_count: usize,
+    /// How many times the ID is in the right list.
+    pub right_count: usize,
+}
+
+impl Contribution {
+    /// The ID times the number of times it's on the right, once for every time it's on the left.
+    ///
+    /// This is worked out in a `u128`, so it's only an [`Overflow`] if the ID is in both lists
+    /// billions of times.
+    pub fn score(&self) -> Result<u128, Overflow> {
+        (self.id as u128 * self.right_count as u128)
+            .checked_mul(self.left_count as u128)
+            .ok_or_else(|| Overflow::new::<u128>("the contribution to the similarity score"))
+    }
+}
+
+/// Why two lists are as far apart, or as similar, as they are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// The sorted pairs which are furthest apart, furthest first.
+    pub furthest_pairs: Vec<(usize, usize)>,
+    /// The IDs on the left which add the most to the similarity score, biggest first. IDs which
+    /// add nothing are left out.
+    pub biggest_contributions: Vec<Contribution>,
+    /// Every ID which is only in the left list, from smallest to largest, without repeats.
+    pub only_left: Vec<usize>,
+    /// Every ID which is only in the right list, from smallest to largest, without repeats.
+    pub only_right: Vec<usize>,
+    /// How many of each thing to show when printing.
+    top: usize,
+}
+
+impl LocationLists {
+    /// Find the `top` pairs which add the most to the distance and the `top` IDs which add the most
+    /// to the similarity score, along with the IDs which are only in one of the lists.
+    ///
+    /// Ties keep the order of the sorted lists.
+    pub fn explain(&self, top: usize) -> Explanation {
+        let mut furthest_pairs: Vec<(usize, usize)> = self.pairs().collect();
+        furthest_pairs.sort_by_key(|(a, b)| std::cmp::Reverse(a.abs_diff(*b)));
+        furthest_pairs.truncate(top);
+
+        let (left_runs, right_runs) = (runs(self.left()), runs(self.right()));
+        let mut biggest_contributions: Vec<Contribution> = left_runs
+            .iter()
+            .filter_map(|&(id, left_count)| {
+                let right_count = count_of(&right_runs, id)?;
+                Some(Contribution {
+                    id,
+                    left_count,
+                    right_count,
+                })
+            })
+            .collect();
+        // A contribution which is too big to work out is bigger than any which isn't.
+        biggest_contributions.sort_by_key(|c| std::cmp::Reverse(c.score().unwrap_or(u128::MAX)));
+        biggest_contributions.truncate(top);
+
+        let only = |runs: &[(usize, usize)], other: &[(usize, usize)]| -> Vec<usize> {
+            runs.iter()
+                .map(|&(id, _)| id)
+                .filter(|&id| count_of(other, id).is_none())
+                .collect()
+        };
+
+        Explanation {
+            furthest_pairs,
+            biggest_contributions,
+            only_left: only(&left_runs, &right_runs),
+            only_right: only(&right_runs, &left_runs),
+            top,
+        }
+    }
+}
+
+/// Each ID in a sorted list, with how many times it appears.
+fn runs(sorted: &[usize]) -> Vec<(usize, usize)> {
+    sorted
+        .chunk_by(|a, b| a == b)
+        .map(|run| (run[0], run.len()))
+        .collect()
+}
+
+/// How many times an ID appears, if it does at all.
+fn count_of(runs: &[(usize, usize)], id: usize) -> Option<usize> {
+    runs.binary_search_by_key(&id, |&(id, _)| id)
+        .ok()
+        .map(|i| runs[i].1)
+}
+
+impl Display for Explanation {
+    /// Only the first `top` of the IDs which are in one list are shown, so that lists with hardly
+    /// anything in common don't print every single ID.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Furthest apart pairs:")?;
+        for (a, b) in &self.furthest_pairs {
+            writeln!(f, "  {a} and {b}: {}", a.abs_diff(*b))?;
+        }
+
+        writeln!(f, "\nBiggest contributions to the similarity score:")?;
+        for c in &self.biggest_contributions {
+            let score = match c.score() {
+                Ok(score) => score.to_string(),
+                Err(_) => "too big to count".to_owned(),
+            };
+            writeln!(
+                f,
+                "  {score}: {} × {} on the right × {} on the left",
+                c.id, c.right_count, c.left_count
+            )?;
+        }
+
+        for (side, ids) in [("left", &self.only_left), ("right", &self.only_right)] {
+            writeln!(f, "\nOnly in the {side} list ({} IDs):", ids.len())?;
+            let shown: Vec<String> = ids.iter().take(self.top).map(usize::to_string).collect();
+            if !shown.is_empty() {
+                write!(f, "  {}", shown.join(", "))?;
+                if ids.len() > self.top {
+                    write!(f, ", and {} more", ids.len() - self.top)?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Solution;
+
+    use super::*;
+    use crate::Day01;
+
+    #[test]
+    fn sample() {
+        let lists = Day01::parse(include_str!("../../samples/day01.txt")).unwrap();
+        let explanation = lists.explain(2);
+        // The sorted pairs are (1, 3), (2, 3), (3, 3), (3, 4), (3, 5) and (4, 9).
+        assert_eq!(explanation.furthest_pairs, [(4, 9), (1, 3)]);
+        let contributions = &explanation.biggest_contributions;
+        let scores: Vec<u128> = contributions.iter().map(|c| c.score().unwrap()).collect();
+        assert_eq!(scores, [27, 4]);
+        assert_eq!(explanation.only_left, [1, 2]);
+        assert_eq!(explanation.only_right, [5, 9]);
+    }
+
+    #[test]
+    fn huge_contribution() {
+        let lists = LocationLists::new(vec![usize::MAX; 2], vec![usize::MAX; 2]);
+        let explanation = lists.explain(1);
+        let score = explanation.biggest_contributions[0].score();
+        assert_eq!(score, Ok(4 * u128::from(u64::MAX)));
+        assert!(explanation.to_string().contains(&format!(
+            "  {}: {} ×",
+            4 * u128::from(u64::MAX),
+            usize::MAX
+        )));
+
+        let contribution = Contribution {
+            id: usize::MAX,
+            left_count: usize::MAX,
+            right_count: 2,
+        };
+        assert!(contribution.score().is_err());
+    }
+}
@@ -0,0 +1,433 @@
+//! Location lists which are too big to fit in memory.
+//!
+//! We read the lists a line at a time, sorting the IDs in batches which fit in the memory we're
+//! allowed, and writing each sorted batch (a "run") to a temporary file. Once everything's been
+//! read, merging all the runs of a list gives back the whole list in sorted order, one ID at a
+//! time, which is all that the distance and the similarity score need.
+//!
+//! Every run being merged is an open file, so if there are more than [`MAX_MERGE`] of them, we
+//! first merge them that many at a time into longer runs, as many times as it takes.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use aoc_core::ParseError;
+
+use crate::columns::parse_line;
+use crate::{DistanceMetric, Overflow};
+
+/// The size of an ID in a run file.
+const ID_BYTES: usize = size_of::<u64>();
+
+/// The most runs we merge at once, which keeps us well below the usual limits on open files, even
+/// with both lists being merged together.
+const MAX_MERGE: usize = 64;
+
+#[derive(Debug)]
+pub enum ExternalError {
+    Io(std::io::Error),
+    Parse(ParseError),
+    Overflow(Overflow),
+}
+
+impl Display for ExternalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExternalError::Io(e) => write!(f, "{e}"),
+            ExternalError::Parse(e) => write!(f, "{e}"),
+            ExternalError::Overflow(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ExternalError {}
+
+impl From<std::io::Error> for ExternalError {
+    fn from(e: std::io::Error) -> Self {
+        ExternalError::Io(e)
+    }
+}
+
+impl From<ParseError> for ExternalError {
+    fn from(e: ParseError) -> Self {
+        ExternalError::Parse(e)
+    }
+}
+
+impl From<Overflow> for ExternalError {
+    fn from(e: Overflow) -> Self {
+        ExternalError::Overflow(e)
+    }
+}
+
+/// A temporary directory which is deleted, along with everything in it, when it's dropped.
+#[derive(Debug)]
+struct SpillDir {
+    path: PathBuf,
+}
+
+impl SpillDir {
+    fn new() -> std::io::Result<Self> {
+        // Several lists could be spilling at once, even within one process.
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "day01-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = std::env::temp_dir().join(name);
+        std::fs::create_dir_all(&path)?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        // There's nothing useful we can do if this fails, and it's only a temporary directory.
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// One list, spilled to disk as sorted runs.
+#[derive(Debug)]
+struct SpilledList {
+    runs: Vec<PathBuf>,
+    /// The IDs which haven't been spilled yet.
+    buffer: Vec<usize>,
+}
+
+impl SpilledList {
+    fn with_batch(batch: usize) -> Self {
+        Self {
+            runs: Vec::new(),
+            // We allocate it all now, since growing it could need nearly twice as much.
+            buffer: Vec::with_capacity(batch),
+        }
+    }
+
+    /// Sort the buffer and write it out as a new run.
+    fn spill(&mut self, dir: &Path, name: &str) -> std::io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        self.buffer.sort_unstable();
+        let path = dir.join(format!("{name}-{}", self.runs.len()));
+        let mut writer = BufWriter::new(File::create(&path)?);
+        for id in self.buffer.drain(..) {
+            write_id(&mut writer, id)?;
+        }
+        writer.flush()?;
+        self.runs.push(path);
+        Ok(())
+    }
+
+    /// Merge the runs [`MAX_MERGE`] at a time into longer ones, until there are few enough to
+    /// merge all at once, using about `memory` bytes for the buffers.
+    fn reduce(&mut self, dir: &Path, name: &str, memory: usize) -> std::io::Result<()> {
+        for pass in 0.. {
+            if self.runs.len() <= MAX_MERGE {
+                break;
+            }
+            let mut merged: Vec<PathBuf> = Vec::new();
+            for batch in self.runs.chunks(MAX_MERGE) {
+                let path = dir.join(format!("{name}-{pass}-{}", merged.len()));
+                let mut merge = Merge::open(batch, memory)?;
+                let mut writer = BufWriter::new(File::create(&path)?);
+                while let Some(id) = merge.next()? {
+                    write_id(&mut writer, id)?;
+                }
+                writer.flush()?;
+                // The longer run has everything in these, so we don't need them taking up space.
+                drop(merge);
+                for run in batch {
+                    std::fs::remove_file(run)?;
+                }
+                merged.push(path);
+            }
+            self.runs = merged;
+        }
+        Ok(())
+    }
+
+    /// Merge the runs back together, using about `memory` bytes for the buffers.
+    fn merge(&self, memory: usize) -> std::io::Result<Merge> {
+        Merge::open(&self.runs, memory)
+    }
+}
+
+fn write_id(run: &mut impl Write, id: usize) -> std::io::Result<()> {
+    run.write_all(&(id as u64).to_le_bytes())
+}
+
+fn read_id(run: &mut impl Read) -> std::io::Result<Option<usize>> {
+    let mut bytes = [0; ID_BYTES];
+    match run.read_exact(&mut bytes) {
+        Ok(()) => Ok(Some(u64::from_le_bytes(bytes) as usize)),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// A k-way merge of sorted runs, giving back every ID in all of them in sorted order.
+struct Merge {
+    runs: Vec<BufReader<File>>,
+    /// The next ID of every run which has any left, along with which run it's from.
+    heap: BinaryHeap<Reverse<(usize, usize)>>,
+}
+
+impl Merge {
+    /// Open every run to merge them, using about `memory` bytes for the buffers.
+    fn open(paths: &[PathBuf], memory: usize) -> std::io::Result<Self> {
+        let capacity = (memory / paths.len().max(1)).clamp(ID_BYTES, 1 << 16);
+        let mut merge = Merge {
+            runs: Vec::with_capacity(paths.len()),
+            heap: BinaryHeap::with_capacity(paths.len()),
+        };
+        for path in paths {
+            let mut run = BufReader::with_capacity(capacity, File::open(path)?);
+            if let Some(id) = read_id(&mut run)? {
+                merge.heap.push(Reverse((id, merge.runs.len())));
+            }
+            merge.runs.push(run);
+        }
+        Ok(merge)
+    }
+
+    fn peek(&self) -> Option<usize> {
+        self.heap.peek().map(|Reverse((id, _))| *id)
+    }
+
+    fn next(&mut self) -> std::io::Result<Option<usize>> {
+        let Some(Reverse((id, run))) = self.heap.pop() else {
+            return Ok(None);
+        };
+        if let Some(next) = read_id(&mut self.runs[run])? {
+            self.heap.push(Reverse((next, run)));
+        }
+        Ok(Some(id))
+    }
+
+    /// The next ID along with how many times in a row it appears.
+    fn next_run(&mut self) -> std::io::Result<Option<(usize, usize)>> {
+        let Some(id) = self.next()? else {
+            return Ok(None);
+        };
+        let mut count = 1;
+        while self.peek() == Some(id) {
+            self.next()?;
+            count += 1;
+        }
+        Ok(Some((id, count)))
+    }
+}
+
+/// The two lists of location IDs, kept on disk rather than in memory.
+///
+/// This gives the same answers as [`LocationLists`](crate::LocationLists), while never holding
+/// much more than `memory_limit` bytes of IDs in memory at once.
+#[derive(Debug)]
+pub struct ExternalLists {
+    left: SpilledList,
+    right: SpilledList,
+    len: usize,
+    memory_limit: usize,
+    /// Where the runs are, which is deleted along with the lists.
+    _dir: SpillDir,
+}
+
+impl ExternalLists {
+    /// Read the lists a line at a time, in the same format as for
+    /// [`LocationLists`](crate::LocationLists).
+    ///
+    /// Trailing whitespace and a byte order mark are ignored, like they would be after
+    /// normalising.
+    pub fn from_reader(
+        mut reader: impl BufRead,
+        memory_limit: usize,
+    ) -> Result<Self, ExternalError> {
+        let dir = SpillDir::new()?;
+        // Each list gets half of the memory.
+        let batch = (memory_limit / 2 / size_of::<usize>()).max(1);
+        let mut left = SpilledList::with_batch(batch);
+        let mut right = SpilledList::with_batch(batch);
+
+        let mut line = String::new();
+        let mut len = 0;
+        for i in 0.. {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            let mut text = line.trim_end();
+            if i == 0 {
+                text = text.trim_start_matches('\u{feff}');
+            }
+            let ids = parse_line(i, text, Some(2))?;
+            left.buffer.push(ids[0]);
+            right.buffer.push(ids[1]);
+            len += 1;
+            if left.buffer.len() >= batch {
+                left.spill(&dir.path, "left")?;
+                right.spill(&dir.path, "right")?;
+            }
+        }
+        left.spill(&dir.path, "left")?;
+        right.spill(&dir.path, "right")?;
+        // The buffers aren't needed any more, so we give their memory back.
+        left.buffer = Vec::new();
+        right.buffer = Vec::new();
+        // Only one list is being merged at a time here, so it can have all of the memory.
+        left.reduce(&dir.path, "left", memory_limit)?;
+        right.reduce(&dir.path, "right", memory_limit)?;
+
+        Ok(Self {
+            left,
+            right,
+            len,
+            memory_limit,
+            _dir: dir,
+        })
+    }
+
+    /// The number of IDs in each list.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of sorted runs each list is kept as. If a list was spilled as too many runs to
+    /// open at once, this is how many are left after merging them into longer ones.
+    pub fn runs(&self) -> usize {
+        self.left.runs.len()
+    }
+
+    fn merges(&self) -> std::io::Result<(Merge, Merge)> {
+        let memory = self.memory_limit / 2;
+        Ok((self.left.merge(memory)?, self.right.merge(memory)?))
+    }
+
+    /// The total distance between the lists.
+    pub fn total_distance(&self) -> Result<usize, ExternalError> {
+        self.distance(DistanceMetric::Absolute)
+    }
+
+    /// The distance between the lists, measured with any metric.
+    pub fn distance(&self, metric: DistanceMetric) -> Result<usize, ExternalError> {
+        let (mut left, mut right) = self.merges()?;
+        let mut error = None;
+        let pairs = std::iter::from_fn(|| match (left.next(), right.next()) {
+            (Ok(Some(a)), Ok(Some(b))) => Some((a, b)),
+            (Err(e), _) | (_, Err(e)) => {
+                error = Some(e);
+                None
+            }
+            _ => None,
+        });
+        let distance = metric.distance_of_pairs(pairs);
+        match error {
+            Some(e) => Err(e.into()),
+            None => Ok(distance?),
+        }
+    }
+
+    /// The similarity score of the lists.
+    pub fn similarity_score(&self) -> Result<usize, ExternalError> {
+        let (mut left, mut right) = self.merges()?;
+        let mut score: usize = 0;
+        let mut right_run = right.next_run()?;
+        while let Some((id, left_count)) = left.next_run()? {
+            while right_run.is_some_and(|(r, _)| r < id) {
+                right_run = right.next_run()?;
+            }
+            if let Some((r, right_count)) = right_run {
+                if r == id {
+                    score = left_count
+                        .checked_mul(right_count)
+                        .and_then(|count| id.checked_mul(count))
+                        .and_then(|product| score.checked_add(product))
+                        .ok_or_else(|| Overflow::new::<usize>("the similarity score"))?;
+                }
+            }
+        }
+        Ok(score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    use super::*;
+    use crate::LocationLists;
+
+    #[test]
+    fn matches_in_memory() {
+        let mut rng = StdRng::seed_from_u64(17);
+        for _ in 0..20 {
+            let len = rng.random_range(0..500);
+            let input: String = (0..len)
+                .map(|_| {
+                    let (a, b) = (rng.random_range(0..100), rng.random_range(0..100));
+                    format!("{a}   {b}\n")
+                })
+                .collect();
+            // Only room for 16 IDs from each list at a time.
+            let external = ExternalLists::from_reader(input.as_bytes(), 256).unwrap();
+            let lists: LocationLists = input.parse().unwrap();
+            assert_eq!(external.len(), len);
+            assert_eq!(external.runs(), len.div_ceil(16));
+            for metric in DistanceMetric::ALL {
+                assert_eq!(
+                    external.distance(metric).unwrap(),
+                    lists.distance(metric).unwrap()
+                );
+            }
+            assert_eq!(
+                external.similarity_score().unwrap(),
+                lists.similarity_score().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn many_runs() {
+        let mut rng = StdRng::seed_from_u64(64);
+        let input: String = (0..(3 * MAX_MERGE + 5))
+            .map(|_| {
+                let (a, b) = (rng.random_range(0..100), rng.random_range(0..100));
+                format!("{a}   {b}\n")
+            })
+            .collect();
+        // Only room for one ID from each list at a time, so every line is a run of its own, and
+        // they have to be merged in more than one go.
+        let external = ExternalLists::from_reader(input.as_bytes(), 16).unwrap();
+        let lists: LocationLists = input.parse().unwrap();
+        assert_eq!(external.runs(), 4);
+        assert_eq!(
+            external.total_distance().unwrap(),
+            lists.total_distance().unwrap()
+        );
+        assert_eq!(
+            external.similarity_score().unwrap(),
+            lists.similarity_score().unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        let error = ExternalLists::from_reader("3   4\n4\n".as_bytes(), 256).unwrap_err();
+        let ExternalError::Parse(error) = error else {
+            panic!("expected a parse error, got {error}");
+        };
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+}
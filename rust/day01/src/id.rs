@@ -0,0 +1,82 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+use aoc_core::{Answer, SolveError};
+
+/// A type which location IDs can be, along with the arithmetic we need to do on them.
+///
+/// Every answer is worked out in the same type as the IDs, checking every step so that an answer
+/// which doesn't fit is an [`Overflow`] rather than something which has silently wrapped around.
+pub trait LocationId: Copy + Ord + Hash + Debug + Display + FromStr + Into<Answer> {
+    const ZERO: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    /// `|self - other|`, if it fits.
+    fn checked_distance(self, other: Self) -> Option<Self>;
+
+    /// The number of times an ID appears, as the same type as the IDs, if it fits.
+    fn from_count(count: usize) -> Option<Self>;
+}
+
+macro_rules! location_id {
+    ($($t:ty),*) => {
+        $(
+            impl LocationId for $t {
+                const ZERO: Self = 0;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn checked_distance(self, other: Self) -> Option<Self> {
+                    self.max(other).checked_sub(self.min(other))
+                }
+
+                fn from_count(count: usize) -> Option<Self> {
+                    Self::try_from(count).ok()
+                }
+            }
+        )*
+    };
+}
+
+location_id!(usize, u64, i64, i128, u128);
+
+/// An answer which is too big for the type of the IDs it was worked out from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    /// Which answer it was, like "the similarity score".
+    pub answer: &'static str,
+    pub id_type: &'static str,
+}
+
+impl Overflow {
+    pub(crate) fn new<T: LocationId>(answer: &'static str) -> Self {
+        Self {
+            answer,
+            id_type: std::any::type_name::<T>(),
+        }
+    }
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is too big to fit in {}", self.answer, self.id_type)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+impl From<Overflow> for SolveError {
+    fn from(overflow: Overflow) -> Self {
+        SolveError(overflow.to_string())
+    }
+}
@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+
+use crate::{LocationLists, Overflow};
+
+/// Which of the two lists an ID is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// The two lists of location IDs, for when IDs are added and taken away one at a time, keeping
+/// the total distance and the similarity score up to date as they go.
+///
+/// The similarity score is easy, since adding an ID only changes its own term. The distance is
+/// harder, since adding one ID shifts every sorted pair after it along by one. Instead we use the
+/// fact that, for lists of the same length, the total distance is the area between their
+/// cumulative counts: the sum over every `t` of `|#(left ≤ t) - #(right ≤ t)|`. Adding an ID `x`
+/// on the left adds one to that difference for every `t ≥ x`, which a [`Profile`] can do in about
+/// `√n` steps, where `n` is the number of different IDs we've seen.
+///
+/// Both answers are kept in wider types than `usize`, so that they can be too big for a while
+/// and come back down as IDs are removed. They're only an [`Overflow`] if they're too big when
+/// they're asked for.
+#[derive(Debug, Clone, Default)]
+pub struct LocationIndex {
+    left_counts: HashMap<usize, usize>,
+    right_counts: HashMap<usize, usize>,
+    left_len: usize,
+    right_len: usize,
+    similarity: u128,
+    profile: Profile,
+}
+
+impl LocationIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of IDs in one of the lists.
+    pub fn len(&self, side: Side) -> usize {
+        match side {
+            Side::Left => self.left_len,
+            Side::Right => self.right_len,
+        }
+    }
+
+    /// Whether both lists are empty.
+    pub fn is_empty(&self) -> bool {
+        self.left_len == 0 && self.right_len == 0
+    }
+
+    /// How many times an ID is in one of the lists.
+    pub fn count(&self, side: Side, id: usize) -> usize {
+        let counts = match side {
+            Side::Left => &self.left_counts,
+            Side::Right => &self.right_counts,
+        };
+        counts.get(&id).copied().unwrap_or(0)
+    }
+
+    /// The total distance between the lists, or [`Option::None`] if they're different lengths,
+    /// since then they can't be paired up.
+    pub fn total_distance(&self) -> Option<Result<usize, Overflow>> {
+        (self.left_len == self.right_len).then(|| {
+            usize::try_from(self.profile.total)
+                .map_err(|_| Overflow::new::<usize>("the total distance"))
+        })
+    }
+
+    pub fn similarity_score(&self) -> Result<usize, Overflow> {
+        usize::try_from(self.similarity).map_err(|_| Overflow::new::<usize>("the similarity score"))
+    }
+
+    /// Add an ID to one of the lists.
+    ///
+    /// This fails, without changing anything, only if the similarity score would be too big
+    /// even for a `u128`.
+    pub fn insert(&mut self, side: Side, id: usize) -> Result<(), Overflow> {
+        let other = self.count(side.other(), id);
+        self.similarity = self
+            .similarity
+            .checked_add(term(id, other))
+            .ok_or_else(|| Overflow::new::<u128>("the similarity score"))?;
+        *self.counts_mut(side).entry(id).or_default() += 1;
+        *self.len_mut(side) += 1;
+        self.profile.add_from(id, side.sign());
+        Ok(())
+    }
+
+    /// Take one copy of an ID out of one of the lists, returning whether it was there.
+    pub fn remove(&mut self, side: Side, id: usize) -> bool {
+        let counts = self.counts_mut(side);
+        match counts.get_mut(&id) {
+            None => return false,
+            Some(1) => {
+                counts.remove(&id);
+            }
+            Some(count) => *count -= 1,
+        }
+        // Adding this copy of the ID added this much to the score, so it can't go below zero.
+        self.similarity -= term(id, self.count(side.other(), id));
+        *self.len_mut(side) -= 1;
+        self.profile.add_from(id, -side.sign());
+        true
+    }
+
+    fn counts_mut(&mut self, side: Side) -> &mut HashMap<usize, usize> {
+        match side {
+            Side::Left => &mut self.left_counts,
+            Side::Right => &mut self.right_counts,
+        }
+    }
+
+    fn len_mut(&mut self, side: Side) -> &mut usize {
+        match side {
+            Side::Left => &mut self.left_len,
+            Side::Right => &mut self.right_len,
+        }
+    }
+}
+
+/// How much one copy of an ID adds to the similarity score, when there are `count` copies of it
+/// in the other list. Two 64-bit numbers always multiply to something which fits in a `u128`.
+fn term(id: usize, count: usize) -> u128 {
+    id as u128 * count as u128
+}
+
+impl Side {
+    fn other(self) -> Self {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+
+    /// Which way an ID on this side moves the difference between the cumulative counts.
+    fn sign(self) -> i64 {
+        match self {
+            Side::Left => 1,
+            Side::Right => -1,
+        }
+    }
+}
+
+impl From<&LocationLists> for LocationIndex {
+    fn from(lists: &LocationLists) -> Self {
+        let mut index = Self::new();
+        for &id in lists.left() {
+            index
+                .insert(Side::Left, id)
+                .expect("Nothing is on the right yet, so the score is still zero.");
+        }
+        for &id in lists.right() {
+            index
+                .insert(Side::Right, id)
+                .expect("Each ID on the right adds less than a usize to the score in a u128.");
+        }
+        index
+    }
+}
+
+/// A step function `d(t)`, which is zero before the first ID and changes only at IDs, along with
+/// the area between it and zero, `total`.
+///
+/// The steps are stored in order in blocks of about `√n`, so that adding to every step from some
+/// ID onwards means changing a part of one block one step at a time, and then every later block
+/// all at once.
+#[derive(Debug, Clone, Default)]
+struct Profile {
+    blocks: Vec<Block>,
+    /// The number of steps in all of the blocks.
+    steps: usize,
+    /// The sum of `|d(t)|` over every `t`, apart from after the last ID.
+    total: i128,
+}
+
+/// Where `d(t)` is one value, from `start` up to (but not including) the next step's `start`.
+#[derive(Debug, Clone, Copy)]
+struct Step {
+    start: usize,
+    /// The distance to the next step, or zero for the last step, which goes on forever.
+    width: i128,
+    /// The value of `d(t)`, without the block's `offset`.
+    value: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Block {
+    steps: Vec<Step>,
+    /// Added to the value of every step in the block.
+    offset: i64,
+    /// The total width of the steps with each value, without the offset.
+    widths: HashMap<i64, i128>,
+    width: i128,
+    /// The total width of the steps which are below zero, with the offset.
+    negative: i128,
+    /// The sum of `width × |value + offset|` over the steps.
+    area: i128,
+}
+
+impl Block {
+    fn new(steps: Vec<Step>, offset: i64) -> Self {
+        let mut block = Self {
+            steps,
+            offset,
+            ..Self::default()
+        };
+        block.recount();
+        block
+    }
+
+    /// Work out all the totals again from the steps.
+    fn recount(&mut self) {
+        self.widths.clear();
+        self.width = 0;
+        self.negative = 0;
+        self.area = 0;
+        for step in &self.steps {
+            let value = step.value + self.offset;
+            *self.widths.entry(step.value).or_default() += step.width;
+            self.width += step.width;
+            if value < 0 {
+                self.negative += step.width;
+            }
+            self.area += step.width * i128::from(value.abs());
+        }
+    }
+
+    /// Add `delta` to every step, changing `area` by how much that changes each `|value|`.
+    fn add(&mut self, delta: i64) {
+        let width_at = |value: i64| {
+            self.widths
+                .get(&(value - self.offset))
+                .copied()
+                .unwrap_or(0)
+        };
+        if delta > 0 {
+            // Steps at zero or above get further from zero, and the rest get closer.
+            self.area += self.width - 2 * self.negative;
+            self.negative -= width_at(-1);
+        } else {
+            let at_most_zero = self.negative + width_at(0);
+            self.area += 2 * at_most_zero - self.width;
+            self.negative += width_at(0);
+        }
+        self.offset += delta;
+    }
+}
+
+impl Profile {
+    /// The smallest number of steps we bother splitting into blocks.
+    const MIN_BLOCK: usize = 32;
+
+    fn block_size(&self) -> usize {
+        self.steps.isqrt().max(Self::MIN_BLOCK)
+    }
+
+    /// Add `delta`, which is 1 or -1, to `d(t)` for every `t ≥ x`.
+    fn add_from(&mut self, x: usize, delta: i64) {
+        let (b, s) = self.split_at(x);
+
+        let block = &mut self.blocks[b];
+        self.total -= block.area;
+        for step in &mut block.steps[s..] {
+            step.value += delta;
+        }
+        block.recount();
+        self.total += block.area;
+
+        for block in &mut self.blocks[b + 1..] {
+            self.total -= block.area;
+            block.add(delta);
+            self.total += block.area;
+        }
+    }
+
+    /// Make sure there's a step starting at `x`, returning which block it's in and where.
+    fn split_at(&mut self, x: usize) -> (usize, usize) {
+        if self.blocks.is_empty() {
+            self.blocks.push(Block::default());
+        }
+        // The last block which starts at or before `x`, or the first if none do.
+        let b = self
+            .blocks
+            .partition_point(|block| block.steps.first().is_some_and(|s| s.start <= x))
+            .saturating_sub(1);
+        let block = &mut self.blocks[b];
+        let s = block.steps.partition_point(|step| step.start <= x);
+        if s > 0 && block.steps[s - 1].start == x {
+            return (b, s - 1);
+        }
+
+        let new = if s == 0 {
+            // `x` comes before every ID, where `d(t)` is zero. If there's a step after it, it's
+            // the first one in this block.
+            let width = match block.steps.first() {
+                Some(next) => i128::from(next.start as u64) - i128::from(x as u64),
+                None => 0,
+            };
+            Step {
+                start: x,
+                width,
+                value: -block.offset,
+            }
+        } else {
+            let before = &mut block.steps[s - 1];
+            let width = if before.width == 0 {
+                0
+            } else {
+                before.width - (i128::from(x as u64) - i128::from(before.start as u64))
+            };
+            before.width -= width;
+            if before.width == 0 {
+                // `before` was the last step, which goes on forever, and now ends at `x` instead.
+                before.width = i128::from(x as u64) - i128::from(before.start as u64);
+            }
+            Step {
+                start: x,
+                width,
+                value: before.value,
+            }
+        };
+        self.total -= block.area;
+        block.steps.insert(s, new);
+        block.recount();
+        self.total += block.area;
+        self.steps += 1;
+
+        if block.steps.len() > 2 * self.block_size() {
+            self.rebalance();
+            return self.split_at(x);
+        }
+        (b, s)
+    }
+
+    /// Share the steps out into new blocks of the right size.
+    fn rebalance(&mut self) {
+        let size = self.block_size();
+        let steps: Vec<Step> = self
+            .blocks
+            .drain(..)
+            .flat_map(|block| {
+                let offset = block.offset;
+                block.steps.into_iter().map(move |step| Step {
+                    value: step.value + offset,
+                    ..step
+                })
+            })
+            .collect();
+        self.blocks = steps
+            .chunks(size)
+            .map(|chunk| Block::new(chunk.to_vec(), 0))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn matches_recomputing() {
+        let mut rng = StdRng::seed_from_u64(18);
+        let mut index = LocationIndex::new();
+        let (mut left, mut right): (Vec<usize>, Vec<usize>) = (Vec::new(), Vec::new());
+        for _ in 0..3000 {
+            let side = if rng.random_bool(0.5) {
+                Side::Left
+            } else {
+                Side::Right
+            };
+            let list = match side {
+                Side::Left => &mut left,
+                Side::Right => &mut right,
+            };
+            // Remove about a third of the time, so that the lists grow.
+            if !list.is_empty() && rng.random_bool(0.35) {
+                let id = list.swap_remove(rng.random_range(0..list.len()));
+                assert!(index.remove(side, id));
+            } else {
+                let id = rng.random_range(0..200);
+                list.push(id);
+                index.insert(side, id).unwrap();
+            }
+
+            if left.len() == right.len() {
+                let lists = LocationLists::new(left.clone(), right.clone());
+                assert_eq!(index.total_distance(), Some(lists.total_distance()));
+                assert_eq!(index.similarity_score(), lists.similarity_score());
+            } else {
+                assert_eq!(index.total_distance(), None);
+            }
+        }
+        assert!(!index.remove(Side::Left, 1000));
+    }
+
+    #[test]
+    fn overflow() {
+        let mut index = LocationIndex::new();
+        index.insert(Side::Left, usize::MAX).unwrap();
+        index.insert(Side::Right, 0).unwrap();
+        assert_eq!(index.total_distance(), Some(Ok(usize::MAX)));
+        index.insert(Side::Left, usize::MAX).unwrap();
+        index.insert(Side::Right, 0).unwrap();
+        let overflow = index.total_distance().unwrap().unwrap_err();
+        assert_eq!(
+            overflow.to_string(),
+            "the total distance is too big to fit in usize"
+        );
+
+        index.insert(Side::Right, usize::MAX).unwrap();
+        assert!(index.similarity_score().is_err());
+        // Taking IDs away brings both answers back down to something which fits.
+        assert!(index.remove(Side::Left, usize::MAX));
+        assert_eq!(index.similarity_score(), Ok(usize::MAX));
+        assert!(index.remove(Side::Right, 0));
+        assert!(index.remove(Side::Right, 0));
+        assert_eq!(index.total_distance(), Some(Ok(0)));
+    }
+}
@@ -0,0 +1,129 @@
+//! Day 1: Historian Hysteria.
+//!
+//! The puzzle itself is solved by [`LocationLists`], which can also be used on its own to compare
+//! two lists of IDs. [`LocationColumns`] compares any number of lists with each other, and
+//! [`LocationIndex`] keeps the answers up to date as IDs are added and removed.
+
+mod columns;
+mod explain;
+mod external;
+mod id;
+mod index;
+mod lists;
+mod matrix;
+mod metric;
+
+use std::marker::PhantomData;
+
+use aoc_core::{Answer, ParseError, Solution, SolveError};
+
+pub use columns::{LocationColumns, MatrixError};
+pub use explain::{Contribution, Explanation};
+pub use external::{ExternalError, ExternalLists};
+pub use id::{LocationId, Overflow};
+pub use index::{LocationIndex, Side};
+pub use lists::LocationLists;
+pub use matrix::PairMatrix;
+pub use metric::DistanceMetric;
+
+/// The puzzle, with IDs of type `T`. A part fails if its answer doesn't fit in `T`.
+pub struct Day01<T = usize>(PhantomData<T>);
+
+impl<T: LocationId> Solution for Day01<T> {
+    const DAY: u8 = 1;
+    type Input = LocationLists<T>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part1(lists: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(lists.total_distance()?.into())
+    }
+
+    fn part2(lists: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(lists.similarity_score()?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../samples/day01.txt");
+
+    /// How we used to work out part 2, going through the whole right list for every number on
+    /// the left.
+    fn part2_quadratic(left: &[usize], right: &[usize]) -> usize {
+        left.iter()
+            .map(|&n| n * right.iter().filter(|m| **m == n).count())
+            .sum()
+    }
+
+    /// Part 2 from a count of each ID in the right list, which unlike [`part2_quadratic`] is quick
+    /// enough to check a long list against.
+    fn part2_counted(left: &[usize], right: &[usize]) -> usize {
+        let mut counts = HashMap::new();
+        for &id in right {
+            *counts.entry(id).or_insert(0) += 1;
+        }
+        left.iter().map(|n| n * counts.get(n).unwrap_or(&0)).sum()
+    }
+
+    #[test]
+    fn sample() {
+        let input = <Day01>::parse(SAMPLE).unwrap();
+        assert_eq!(<Day01>::part1(&input).unwrap(), 11);
+        assert_eq!(<Day01>::part2(&input).unwrap(), 31);
+        let input = Day01::<i128>::parse(SAMPLE).unwrap();
+        assert_eq!(Day01::<i128>::part1(&input).unwrap(), 11);
+        assert_eq!(Day01::<i128>::part2(&input).unwrap(), 31);
+
+        let input = <Day01>::parse(&format!("{0} {0}\n{0} {0}\n", usize::MAX)).unwrap();
+        assert_eq!(<Day01>::part1(&input).unwrap(), 0);
+        assert_eq!(
+            <Day01>::part2(&input),
+            Err(SolveError(
+                "the similarity score is too big to fit in usize".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn part2_matches_quadratic() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..200 {
+            let len = rng.random_range(0..300);
+            // A small range of IDs means plenty of repeats on both sides.
+            let max = rng.random_range(1..50);
+            let left: Vec<usize> = (0..len).map(|_| rng.random_range(0..max)).collect();
+            let right: Vec<usize> = (0..len).map(|_| rng.random_range(0..max)).collect();
+            let expected = part2_quadratic(&left, &right);
+            assert_eq!(
+                LocationLists::new(left, right).similarity_score(),
+                Ok(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn part2_on_a_million_lines() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let left: Vec<usize> = (0..1_000_000)
+            .map(|_| rng.random_range(10_000..100_000))
+            .collect();
+        let right: Vec<usize> = (0..1_000_000)
+            .map(|_| rng.random_range(10_000..100_000))
+            .collect();
+        let expected = part2_counted(&left, &right);
+        assert_eq!(
+            LocationLists::new(left, right).similarity_score(),
+            Ok(expected)
+        );
+    }
+}
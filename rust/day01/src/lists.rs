@@ -0,0 +1,155 @@
+use std::str::FromStr;
+
+use aoc_core::ParseError;
+
+use crate::columns::parse_columns;
+use crate::metric::DistanceMetric;
+use crate::{LocationId, Overflow};
+
+/// The two lists of location IDs which the historians wrote down.
+///
+/// Neither the distance nor the similarity score cares which order the IDs were written in, so we
+/// keep both lists sorted. The IDs can be any [`LocationId`], and the answers are worked out in
+/// the same type, so that they're an error rather than wrong if they don't fit.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocationLists<T = usize> {
+    left: Vec<T>,
+    right: Vec<T>,
+}
+
+impl<T: LocationId> LocationLists<T> {
+    /// Make the lists from their IDs, in any order.
+    ///
+    /// # Panics
+    ///
+    /// If the lists aren't the same length, since then there's no way to pair them up.
+    pub fn new(mut left: Vec<T>, mut right: Vec<T>) -> Self {
+        assert_eq!(
+            left.len(),
+            right.len(),
+            "both lists should be the same length"
+        );
+        left.sort_unstable();
+        right.sort_unstable();
+        Self { left, right }
+    }
+
+    /// The left list, from smallest to largest.
+    pub fn left(&self) -> &[T] {
+        &self.left
+    }
+
+    /// The right list, from smallest to largest.
+    pub fn right(&self) -> &[T] {
+        &self.right
+    }
+
+    /// The number of IDs in each list.
+    pub fn len(&self) -> usize {
+        self.left.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.left.is_empty()
+    }
+
+    /// Pair up the smallest ID on the left with the smallest on the right, then the second
+    /// smallest with the second smallest, and so on.
+    pub fn pairs(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        std::iter::zip(self.left.iter().copied(), self.right.iter().copied())
+    }
+
+    /// The sum of the distances between the IDs in each of the [`pairs`](Self::pairs).
+    pub fn total_distance(&self) -> Result<T, Overflow> {
+        self.distance(DistanceMetric::Absolute)
+    }
+
+    /// The distance between the lists, measured with any metric.
+    pub fn distance(&self, metric: DistanceMetric) -> Result<T, Overflow> {
+        metric
+            .distance(&self.left, &self.right)
+            .expect("Both lists are the same length.")
+    }
+
+    /// Every ID on the left multiplied by the number of times it appears on the right, all added
+    /// up.
+    pub fn similarity_score(&self) -> Result<T, Overflow> {
+        similarity_score(&self.left, &self.right)
+    }
+}
+
+/// The similarity score of two sorted lists.
+pub(crate) fn similarity_score<T: LocationId>(left: &[T], right: &[T]) -> Result<T, Overflow> {
+    // Since both lists are sorted, we can go through them together a run of equal IDs at a time,
+    // rather than searching the right list for every ID on the left.
+    let mut score = T::ZERO;
+    let mut right = right;
+    for left_run in left.chunk_by(|a, b| a == b) {
+        let id = left_run[0];
+        let skipped = right.partition_point(|&r| r < id);
+        right = &right[skipped..];
+        let count = right.partition_point(|&r| r == id);
+        if count == 0 {
+            continue;
+        }
+        score = count
+            .checked_mul(left_run.len())
+            .and_then(T::from_count)
+            .and_then(|count| id.checked_mul(count))
+            .and_then(|product| score.checked_add(product))
+            .ok_or_else(|| Overflow::new::<T>("the similarity score"))?;
+    }
+    Ok(score)
+}
+
+impl<T: LocationId> FromStr for LocationLists<T> {
+    type Err = ParseError;
+
+    /// Parse the lists from two columns of IDs, separated by whitespace.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        // Both parts rely on there being exactly two numbers on each line.
+        let [left, right]: [Vec<T>; 2] = parse_columns(input, Some(2))?
+            .try_into()
+            .expect("We asked for two columns.");
+        Ok(Self::new(left, right))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs_and_parse_errors() {
+        let lists: LocationLists = "3   4\n4   3\n2   5\n".parse().unwrap();
+        assert_eq!(lists.pairs().collect::<Vec<_>>(), [(2, 3), (3, 4), (4, 5)]);
+        let error = "3   4\n4\n".parse::<LocationLists>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        let error = "3   4 5\n".parse::<LocationLists>().unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+    }
+
+    #[test]
+    fn other_id_types() {
+        let lists: LocationLists<i64> = "-3   4\n4   -3\n-3   -9\n".parse().unwrap();
+        assert_eq!(lists.total_distance(), Ok(6));
+        assert_eq!(lists.similarity_score(), Ok(-2));
+        let error = "-3   4\n".parse::<LocationLists>().unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+
+        let big = u128::from(u64::MAX) + 1;
+        let lists: LocationLists<u128> = format!("{big} {big}\n{big} 1\n").parse().unwrap();
+        assert_eq!(lists.total_distance(), Ok(big - 1));
+        assert_eq!(lists.similarity_score(), Ok(2 * big));
+
+        let half = i64::MAX / 2 + 1;
+        let lists = LocationLists::new(vec![half], vec![half]);
+        assert_eq!(lists.similarity_score(), Ok(half));
+        let lists = LocationLists::new(vec![half, half], vec![half, 0]);
+        let overflow = lists.similarity_score().unwrap_err();
+        assert_eq!(
+            overflow.to_string(),
+            "the similarity score is too big to fit in i64"
+        );
+    }
+}
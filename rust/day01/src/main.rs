@@ -1,52 +1,191 @@
-fn part1(lines: impl Iterator<Item = String>) -> String {
-    let lines_parsed: Vec<Vec<usize>> = lines
-        // We each line at the whitespace. For each line, we now have an iterator of usize, where
-        // each element is one of the numbers.
-        .map(|line| {
-            line.split_whitespace()
-                .map(|s| s.parse().expect("We now AoC has good inputs."))
-                // Ideally we'd now call a function which Haskell calls transpose. But Rust doesn't have
-                // that! So instead we'll have to evaluate it now.
-                .collect()
-        })
-        .collect();
-
-    // We know that there are only two numbers, so we will manually do two loops.
-    let mut left: Vec<usize> = lines_parsed.iter().map(|v| v[0]).collect();
-    let mut right: Vec<usize> = lines_parsed.iter().map(|v| v[1]).collect();
-    left.sort();
-    right.sort();
-
-    // Now we can continue doing this in a one-liner.
-    std::iter::zip(left, right)
-        .map(|(a, b)| a.abs_diff(b))
-        .sum::<usize>()
-        .to_string()
-}
-
-fn part2(lines: impl Iterator<Item = String>) -> String {
-    let lines_parsed: Vec<Vec<usize>> = lines
-        .map(|line| {
-            line.split_whitespace()
-                .map(|s| s.parse().expect("We now AoC has good inputs."))
-                .collect()
-        })
-        .collect();
-
-    let left: Vec<usize> = lines_parsed.iter().map(|v| v[0]).collect();
-    let right: Vec<usize> = lines_parsed.iter().map(|v| v[1]).collect();
-
-    left.into_iter()
-        .map(|n| n * right.iter().filter(|m| **m == n).count())
-        .sum::<usize>()
-        .to_string()
-}
-
-fn main() -> std::io::Result<()> {
-    let lines = std::io::stdin()
-        .lines()
-        .collect::<std::io::Result<Vec<String>>>()?;
-    println!("Part 1: {}", part1(lines.clone().into_iter()));
-    println!("Part 2: {}", part2(lines.into_iter()));
-    Ok(())
+use std::error::Error;
+use std::process::ExitCode;
+
+use aoc_core::{InputArgs, Solution, Source};
+use clap::{Parser, ValueEnum};
+use day01::{Day01, DistanceMetric, ExternalLists, LocationColumns, LocationId, LocationLists};
+
+/// Solves day 1 of Advent of Code 2024, or compares any number of lists of location IDs.
+#[derive(Parser)]
+#[command(name = "day01")]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Instead of the puzzle answers, compare every pair of lists with each other. The input can
+    /// have any number of columns for this.
+    #[arg(long, value_enum, value_name = "KIND")]
+    matrix: Option<MatrixKind>,
+    /// After the answers, show the K pairs which are furthest apart, the K IDs which add the most
+    /// to the similarity score, and the IDs which are only in one list.
+    #[arg(
+        long,
+        value_name = "K",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "10"
+    )]
+    explain: Option<usize>,
+    /// How to measure the distance between two lists, for part 1 and the distance matrix.
+    #[arg(long, value_enum, default_value_t = DistanceMetric::Absolute)]
+    metric: DistanceMetric,
+    /// The type to read the IDs as, and to work out the answers in. It's an error if an answer
+    /// doesn't fit.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "TYPE",
+        default_value_t = IdType::Usize,
+        conflicts_with_all = ["matrix", "explain", "external"]
+    )]
+    ids: IdType,
+    /// Read the lists a line at a time, sorting them on disk in temporary files rather than in
+    /// memory, for lists which are too big to fit in memory.
+    #[arg(long, conflicts_with_all = ["matrix", "explain"])]
+    external: bool,
+    /// How much memory `--external` can use to sort the lists, in bytes or with a K, M or G
+    /// suffix.
+    #[arg(long, value_name = "SIZE", default_value = "64M", value_parser = parse_size, requires = "external")]
+    memory_limit: usize,
+    /// How to print the matrix.
+    #[arg(long, value_enum, default_value_t = MatrixFormat::Text, requires = "matrix")]
+    format: MatrixFormat,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum MatrixKind {
+    /// The total distance between each pair of lists.
+    Distance,
+    /// The similarity score of the row's list against the column's list.
+    Similarity,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum IdType {
+    Usize,
+    I64,
+    I128,
+    U128,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum MatrixFormat {
+    Text,
+    Csv,
+}
+
+/// Parse a number of bytes, which can end in K, M or G for kibibytes, mebibytes or gibibytes.
+fn parse_size(size: &str) -> Result<usize, String> {
+    let (number, multiplier) = match size.char_indices().last() {
+        Some((i, 'K' | 'k')) => (&size[..i], 1 << 10),
+        Some((i, 'M' | 'm')) => (&size[..i], 1 << 20),
+        Some((i, 'G' | 'g')) => (&size[..i], 1 << 30),
+        _ => (size, 1),
+    };
+    number
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| format!("`{size}` isn't a size like 1048576, 512K or 64M"))
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if cli.external {
+        return aoc_core::for_each_source(<Day01>::DAY, &cli.input, |source| {
+            print_external_answers(source, &cli)
+        });
+    }
+    aoc_core::for_each_input(<Day01>::DAY, &cli.input, |source, input| {
+        if let Some(kind) = cli.matrix {
+            return print_matrix(source, input, kind, &cli);
+        }
+        match cli.ids {
+            IdType::Usize => match print_answers::<usize>(source, input, &cli) {
+                Some(lists) => {
+                    if let Some(top) = cli.explain {
+                        print!("\n{}", lists.explain(top));
+                    }
+                    true
+                }
+                None => false,
+            },
+            IdType::I64 => print_answers::<i64>(source, input, &cli).is_some(),
+            IdType::I128 => print_answers::<i128>(source, input, &cli).is_some(),
+            IdType::U128 => print_answers::<u128>(source, input, &cli).is_some(),
+        }
+    })
+}
+
+/// Print the answers, giving back the lists if it could work them out.
+fn print_answers<T: LocationId>(
+    source: &Source,
+    input: &str,
+    cli: &Cli,
+) -> Option<LocationLists<T>> {
+    let lists = match Day01::<T>::parse(input) {
+        Ok(lists) => lists,
+        Err(e) => {
+            eprintln!("error: {source}: {}", e.diagnostic(input));
+            return None;
+        }
+    };
+    let answers = lists
+        .distance(cli.metric)
+        .and_then(|distance| Ok((distance, lists.similarity_score()?)));
+    match answers {
+        Ok((distance, similarity)) => {
+            println!("Part 1: {distance}");
+            println!("Part 2: {similarity}");
+            Some(lists)
+        }
+        Err(e) => {
+            eprintln!("error: {source}: {e}");
+            None
+        }
+    }
+}
+
+fn print_external_answers(source: &Source, cli: &Cli) -> bool {
+    match external_answers(source, cli) {
+        Ok((distance, similarity)) => {
+            println!("Part 1: {distance}");
+            println!("Part 2: {similarity}");
+            true
+        }
+        Err(e) => {
+            eprintln!("error: {source}: {e}");
+            false
+        }
+    }
+}
+
+fn external_answers(source: &Source, cli: &Cli) -> Result<(usize, usize), Box<dyn Error>> {
+    let lists = ExternalLists::from_reader(source.open()?, cli.memory_limit)?;
+    Ok((lists.distance(cli.metric)?, lists.similarity_score()?))
+}
+
+fn print_matrix(source: &Source, input: &str, kind: MatrixKind, cli: &Cli) -> bool {
+    let columns: LocationColumns = match input.parse() {
+        Ok(columns) => columns,
+        Err(e) => {
+            eprintln!("error: {source}: {}", e.diagnostic(input));
+            return false;
+        }
+    };
+    let matrix = match kind {
+        MatrixKind::Distance => columns.distance_matrix(cli.metric),
+        MatrixKind::Similarity => columns.similarity_matrix(),
+    };
+    let matrix = match matrix {
+        Ok(matrix) => matrix,
+        Err(e) => {
+            eprintln!("error: {source}: {e}");
+            return false;
+        }
+    };
+    match cli.format {
+        MatrixFormat::Text => print!("{matrix}"),
+        MatrixFormat::Csv => print!("{}", matrix.to_csv()),
+    }
+    true
 }
@@ -0,0 +1,83 @@
+use std::fmt::Display;
+
+/// A number for every pair of lists, where both the rows and the columns are the lists in the
+/// order they were written, numbered from 1.
+///
+/// It's shown as a table of text with [`Display`], or as CSV with [`PairMatrix::to_csv`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairMatrix {
+    rows: Vec<Vec<usize>>,
+}
+
+impl PairMatrix {
+    /// Make the matrix from its rows. There should be as many rows as columns.
+    pub fn new(rows: Vec<Vec<usize>>) -> Self {
+        debug_assert!(
+            rows.iter().all(|row| row.len() == rows.len()),
+            "it should be square"
+        );
+        Self { rows }
+    }
+
+    pub fn rows(&self) -> &[Vec<usize>] {
+        &self.rows
+    }
+
+    /// The number for list `i` against list `j`, both counting from 0.
+    pub fn get(&self, i: usize, j: usize) -> Option<usize> {
+        self.rows.get(i)?.get(j).copied()
+    }
+
+    /// The matrix as CSV, with the list numbers as the first row and column.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        let header: Vec<String> = (1..=self.rows.len()).map(|n| n.to_string()).collect();
+        csv.push_str(&format!(",{}\n", header.join(",")));
+        for (i, row) in self.rows.iter().enumerate() {
+            let cells: Vec<String> = row.iter().map(usize::to_string).collect();
+            csv.push_str(&format!("{},{}\n", i + 1, cells.join(",")));
+        }
+        csv
+    }
+}
+
+impl Display for PairMatrix {
+    /// Right-align every number under the number of its list.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label_width = self.rows.len().to_string().len();
+        let width = self
+            .rows
+            .iter()
+            .flatten()
+            .map(|n| n.to_string().len())
+            .max()
+            .unwrap_or(0)
+            .max(label_width);
+
+        write!(f, "{:label_width$}", "")?;
+        for j in 1..=self.rows.len() {
+            write!(f, "  {j:>width$}")?;
+        }
+        writeln!(f)?;
+        for (i, row) in self.rows.iter().enumerate() {
+            write!(f, "{:>label_width$}", i + 1)?;
+            for n in row {
+                write!(f, "  {n:>width$}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renderings() {
+        let matrix = PairMatrix::new(vec![vec![0, 11], vec![11, 0]]);
+        assert_eq!(matrix.to_string(), "    1   2\n1   0  11\n2  11   0\n");
+        assert_eq!(matrix.to_csv(), ",1,2\n1,0,11\n2,11,0\n");
+    }
+}
@@ -0,0 +1,190 @@
+use std::fmt::Display;
+
+use crate::{LocationId, Overflow};
+
+/// How to measure the distance between two lists of location IDs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum DistanceMetric {
+    /// The sum of `|a - b|` over the sorted pairs, which is what the puzzle asks for.
+    #[default]
+    Absolute,
+    /// The sum of `(a - b)²` over the sorted pairs, so that a few far apart pairs count for more
+    /// than lots of close ones.
+    Squared,
+    /// The largest `|a - b|` of any sorted pair.
+    Maximum,
+    /// The smallest sum of `|a - b|` over every way of matching up the IDs, not just the sorted
+    /// pairs, where the longer list can have IDs left over.
+    ///
+    /// In one dimension, pairing in sorted order is already the best matching there is, so for
+    /// lists of the same length this is always the same as [`DistanceMetric::Absolute`]. It only
+    /// differs when one list is longer, where it picks which of the longer list's IDs to leave out.
+    ///
+    /// On the command line, only `--matrix` can give it lists of different lengths, by writing
+    /// `-` where a list has run out of IDs.
+    OptimalAssignment,
+}
+
+impl DistanceMetric {
+    pub const ALL: [DistanceMetric; 4] = [
+        DistanceMetric::Absolute,
+        DistanceMetric::Squared,
+        DistanceMetric::Maximum,
+        DistanceMetric::OptimalAssignment,
+    ];
+
+    /// The distance between two sorted lists, or an error if it doesn't fit in the type of the
+    /// IDs.
+    ///
+    /// Returns [`Option::None`] if the lists are different lengths, unless this is
+    /// [`DistanceMetric::OptimalAssignment`], which is the only one that can handle that.
+    pub fn distance<T: LocationId>(self, left: &[T], right: &[T]) -> Option<Result<T, Overflow>> {
+        debug_assert!(
+            left.is_sorted() && right.is_sorted(),
+            "both lists should be sorted"
+        );
+        if self == DistanceMetric::OptimalAssignment {
+            return Some(optimal_assignment(left, right));
+        }
+        if left.len() != right.len() {
+            return None;
+        }
+        let pairs = std::iter::zip(left.iter().copied(), right.iter().copied());
+        Some(self.distance_of_pairs(pairs))
+    }
+
+    /// The distance between two lists of the same length, given the sorted pairs one at a time.
+    ///
+    /// Since the lists are the same length, [`DistanceMetric::OptimalAssignment`] is the same as
+    /// [`DistanceMetric::Absolute`] here.
+    pub(crate) fn distance_of_pairs<T: LocationId>(
+        self,
+        pairs: impl Iterator<Item = (T, T)>,
+    ) -> Result<T, Overflow> {
+        let overflow = || Overflow::new::<T>("the distance");
+        let mut differences = pairs.map(|(a, b)| a.checked_distance(b).ok_or_else(overflow));
+        match self {
+            DistanceMetric::Absolute | DistanceMetric::OptimalAssignment => differences
+                .try_fold(T::ZERO, |total, d| {
+                    total.checked_add(d?).ok_or_else(overflow)
+                }),
+            DistanceMetric::Squared => differences.try_fold(T::ZERO, |total, d| {
+                let d = d?;
+                d.checked_mul(d)
+                    .and_then(|square| total.checked_add(square))
+                    .ok_or_else(overflow)
+            }),
+            DistanceMetric::Maximum => differences.try_fold(T::ZERO, |max, d| Ok(max.max(d?))),
+        }
+    }
+}
+
+impl Display for DistanceMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DistanceMetric::Absolute => write!(f, "absolute"),
+            DistanceMetric::Squared => write!(f, "squared"),
+            DistanceMetric::Maximum => write!(f, "maximum"),
+            DistanceMetric::OptimalAssignment => write!(f, "optimal assignment"),
+        }
+    }
+}
+
+/// The cheapest way to match every ID in the shorter sorted list to a different ID in the longer
+/// one, where matching `a` with `b` costs `|a - b|`.
+fn optimal_assignment<T: LocationId>(left: &[T], right: &[T]) -> Result<T, Overflow> {
+    let (short, long) = if left.len() <= right.len() {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    // Some best matching never crosses over itself, so the `i`th ID of the short list is matched
+    // with the `i + k`th of the long one, where `k` is how many of the long list's IDs we've left
+    // out so far. `best[k]` is the cheapest cost for the IDs we've matched so far with `k` left
+    // out.
+    let spare = long.len() - short.len();
+    let mut best: Vec<T> = vec![T::ZERO; spare + 1];
+    let cost = |total: T, a: T, b: T| {
+        a.checked_distance(b)
+            .and_then(|d| total.checked_add(d))
+            .ok_or_else(|| Overflow::new::<T>("the distance"))
+    };
+    for (i, &a) in short.iter().enumerate() {
+        best[0] = cost(best[0], a, long[i])?;
+        for k in 1..=spare {
+            let matched = cost(best[k], a, long[i + k])?;
+            // Otherwise we leave out one more of the long list before matching `a`.
+            best[k] = matched.min(best[k - 1]);
+        }
+    }
+    Ok(best[spare])
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    use super::*;
+
+    /// Try every way of matching the short list into the long one.
+    fn brute_force(short: &[usize], long: &[usize], used: &mut [bool]) -> usize {
+        let Some((a, rest)) = short.split_first() else {
+            return 0;
+        };
+        let mut best = usize::MAX;
+        for j in 0..long.len() {
+            if !used[j] {
+                used[j] = true;
+                best = best.min(a.abs_diff(long[j]) + brute_force(rest, long, used));
+                used[j] = false;
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn metrics() {
+        let (left, right) = ([1_usize, 2, 3, 3, 3, 4], [3, 3, 3, 4, 5, 9]);
+        let distances = DistanceMetric::ALL.map(|metric| metric.distance(&left, &right));
+        assert_eq!(
+            distances,
+            [Some(Ok(11)), Some(Ok(35)), Some(Ok(5)), Some(Ok(11))]
+        );
+        assert_eq!(DistanceMetric::Absolute.distance(&[1_usize, 2], &[1]), None);
+        assert_eq!(
+            DistanceMetric::OptimalAssignment.distance(&[1_usize, 5, 9], &[5]),
+            Some(Ok(0))
+        );
+        assert_eq!(
+            DistanceMetric::Squared.distance(&[-5_i64], &[i64::MAX / 2]),
+            Some(Err(Overflow::new::<i64>("the distance")))
+        );
+        assert_eq!(
+            DistanceMetric::Absolute.distance(&[-5_i64, 3], &[-1, 1]),
+            Some(Ok(6))
+        );
+    }
+
+    #[test]
+    fn optimal_assignment_is_optimal() {
+        let mut rng = StdRng::seed_from_u64(15);
+        for _ in 0..300 {
+            let mut short: Vec<usize> = (0..rng.random_range(0..5))
+                .map(|_| rng.random_range(0..20))
+                .collect();
+            let mut long: Vec<usize> = (0..short.len() + rng.random_range(0..3))
+                .map(|_| rng.random_range(0..20))
+                .collect();
+            short.sort_unstable();
+            long.sort_unstable();
+            let expected = brute_force(&short, &long, &mut vec![false; long.len()]);
+            assert_eq!(
+                optimal_assignment(&short, &long),
+                Ok(expected),
+                "{short:?} {long:?}"
+            );
+            assert_eq!(optimal_assignment(&long, &short), Ok(expected));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Checking that [`ExternalLists`] really does stay within its memory limit.
+//!
+//! This is its own test binary so that it can count every allocation without any other tests
+//! allocating at the same time.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+
+use aoc_core::measure::{measure, CountingAllocator};
+use day01::{DistanceMetric, ExternalLists, LocationLists};
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const MEMORY_LIMIT: usize = 256 * 1024;
+const LINES: usize = 400_000;
+
+#[test]
+fn stays_within_memory_limit() {
+    let path = std::env::temp_dir().join(format!("day01-external-{}.txt", std::process::id()));
+    let mut rng = StdRng::seed_from_u64(17);
+    let mut writer = BufWriter::new(File::create(&path).unwrap());
+    for _ in 0..LINES {
+        let left: u32 = rng.random_range(10_000..100_000);
+        let right: u32 = rng.random_range(10_000..100_000);
+        writeln!(writer, "{left}   {right}").unwrap();
+    }
+    writer.flush().unwrap();
+    drop(writer);
+
+    let ((distance, similarity), measurement) = measure(|| {
+        let reader = BufReader::new(File::open(&path).unwrap());
+        let lists = ExternalLists::from_reader(reader, MEMORY_LIMIT).unwrap();
+        assert_eq!(lists.len(), LINES);
+        (
+            lists.distance(DistanceMetric::Absolute).unwrap(),
+            lists.similarity_score().unwrap(),
+        )
+    });
+    // Holding both lists in memory would take 25 times the limit.
+    let in_memory = LINES * 2 * size_of::<usize>();
+    assert!(in_memory > 20 * MEMORY_LIMIT);
+    assert!(
+        measurement.peak_heap < 2 * MEMORY_LIMIT,
+        "used {} bytes, with a limit of {MEMORY_LIMIT}",
+        measurement.peak_heap
+    );
+
+    let lists: LocationLists = std::fs::read_to_string(&path).unwrap().parse().unwrap();
+    assert_eq!(Ok(distance), lists.total_distance());
+    assert_eq!(Ok(similarity), lists.similarity_score());
+    std::fs::remove_file(&path).unwrap();
+}
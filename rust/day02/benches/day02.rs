@@ -0,0 +1,41 @@
+use aoc_core::bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day02::Day02;
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
+const SAMPLE: &str = include_str!("../../samples/day02.txt");
+
+/// Reports of five to eight levels which are mostly safe, like the real input but ten times as
+/// many of them.
+fn generated_input() -> String {
+    let mut rng = StdRng::seed_from_u64(2);
+    let mut input = String::new();
+    for _ in 0..10_000 {
+        let length = rng.random_range(5..=8);
+        let increasing = rng.random_bool(0.5);
+        let mut level: i32 = rng.random_range(20..80);
+        let mut levels: Vec<String> = Vec::new();
+        for _ in 0..length {
+            levels.push(level.to_string());
+            // Mostly safe steps, with the occasional bad one.
+            let step = if rng.random_bool(0.9) {
+                rng.random_range(1..=3)
+            } else {
+                rng.random_range(-2..=6)
+            };
+            level = (level + if increasing { step } else { -step }).max(1);
+        }
+        input.push_str(&levels.join(" "));
+        input.push('\n');
+    }
+    input
+}
+
+fn bench(c: &mut Criterion) {
+    bench_solution::<Day02>(c, "sample", SAMPLE);
+    bench_solution::<Day02>(c, "generated", &generated_input());
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
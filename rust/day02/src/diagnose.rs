@@ -0,0 +1,135 @@
+//! Explaining why a report isn't safe, and how it could be fixed.
+
+use std::fmt::Display;
+
+use crate::rules::Heading;
+use crate::{Rule, SafetyRules};
+
+/// The first step in a report which breaks a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    /// The index of the level which the step starts from.
+    pub index: usize,
+    /// The levels which the step goes from and to.
+    pub levels: [usize; 2],
+    pub rule: Rule,
+}
+
+/// Whether a report is safe, and if not, why not and which levels the Problem Dampener could
+/// remove to fix it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnosis {
+    /// The first rule the report breaks, or [`Option::None`] if it's safe.
+    pub violation: Option<Violation>,
+    /// The index of every level which could be removed to make an unsafe report safe, in order.
+    /// This is empty for a safe report.
+    pub fixes: Vec<usize>,
+}
+
+impl Diagnosis {
+    pub fn is_safe(&self) -> bool {
+        self.violation.is_none()
+    }
+}
+
+impl SafetyRules {
+    /// Work out what, if anything, is wrong with a report.
+    pub fn diagnose(&self, nums: &[usize]) -> Diagnosis {
+        let violations: Vec<Option<Violation>> = self
+            .direction
+            .headings()
+            .iter()
+            .map(|&heading| self.first_violation(nums, heading))
+            .collect();
+        if violations.contains(&None) {
+            return Diagnosis {
+                violation: None,
+                fixes: Vec::new(),
+            };
+        }
+        // When the report could go either way, we blame the way which it gets furthest going,
+        // which for the puzzle's rules is the way its first step goes. If they get as far as
+        // each other, a step of the wrong size says more than a step the wrong way.
+        let violation = violations
+            .into_iter()
+            .flatten()
+            .max_by_key(|v| (v.index, !matches!(v.rule, Rule::Direction { .. })));
+        Diagnosis {
+            violation,
+            fixes: self.dampener_fixes(nums),
+        }
+    }
+
+    fn first_violation(&self, nums: &[usize], heading: Heading) -> Option<Violation> {
+        nums.windows(2).enumerate().find_map(|(index, w)| {
+            self.broken_rule(w[0], w[1], heading).map(|rule| Violation {
+                index,
+                levels: [w[0], w[1]],
+                rule,
+            })
+        })
+    }
+}
+
+impl Display for Diagnosis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(violation) = self.violation else {
+            return write!(f, "safe");
+        };
+        let [a, b] = violation.levels;
+        write!(
+            f,
+            "unsafe from index {} to {} ({a} to {b}): {}; ",
+            violation.index,
+            violation.index + 1,
+            violation.rule
+        )?;
+        match self.fixes.as_slice() {
+            [] => write!(f, "removing one level can't fix it"),
+            [fix] => write!(f, "removing index {fix} fixes it"),
+            [rest @ .., last] => {
+                let rest: Vec<String> = rest.iter().map(usize::to_string).collect();
+                write!(f, "removing index {} or {last} fixes it", rest.join(", "))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample() {
+        let rules = SafetyRules::default();
+        let diagnoses: Vec<String> = include_str!("../../samples/day02.txt")
+            .lines()
+            .map(|line| {
+                let nums: Vec<usize> = line.split(' ').map(|s| s.parse().unwrap()).collect();
+                rules.diagnose(&nums).to_string()
+            })
+            .collect();
+        assert_eq!(
+            diagnoses,
+            [
+                "safe",
+                "unsafe from index 1 to 2 (2 to 7): a step of 5 is more than the largest of 3; \
+                removing one level can't fix it",
+                "unsafe from index 2 to 3 (6 to 2): a step of 4 is more than the largest of 3; \
+                removing one level can't fix it",
+                "unsafe from index 1 to 2 (3 to 2): it goes down in an increasing report; \
+                removing index 1 or 2 fixes it",
+                "unsafe from index 2 to 3 (4 to 4): the level stays the same; removing index 2 or \
+                3 fixes it",
+                "safe",
+            ]
+        );
+        let diagnosis = rules.diagnose(&[1, 2, 3, 1]);
+        assert_eq!(diagnosis.fixes, [3]);
+        assert_eq!(
+            rules.diagnose(&[3, 4, 2]).fixes,
+            [0, 1, 2],
+            "either way round should be tried"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Day 2: Red-Nosed Reports.
+//!
+//! Each [`Report`] knows whether it's safe by the puzzle's rules, and [`SafetyRules`] can check
+//! reports against any others.
+
+mod diagnose;
+mod report;
+mod rules;
+mod stats;
+
+use aoc_core::{Answer, ParseError, Solution, SolveError};
+
+pub use diagnose::{Diagnosis, Violation};
+pub use report::{Report, Reports, Safety, Summary};
+pub use rules::{Direction, Rule, RulesError, SafetyRules};
+pub use stats::Stats;
+
+/// Whether a report can be made safe by removing at most `k` levels from it, with the puzzle's
+/// rules. See [`SafetyRules::is_safe_with_removals`].
+pub fn is_safe_with_removals(nums: &[usize], k: usize) -> bool {
+    SafetyRules::default().is_safe_with_removals(nums, k)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = Reports;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part1(reports: &Self::Input) -> Result<Answer, SolveError> {
+        let safe = reports.iter().filter(|report| report.is_safe()).count();
+        Ok(safe.into())
+    }
+
+    fn part2(reports: &Self::Input) -> Result<Answer, SolveError> {
+        let safe = reports
+            .iter()
+            .filter(|report| report.dampened_safety() != Safety::Unsafe)
+            .count();
+        Ok(safe.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    use super::*;
+
+    const SAMPLE: &str = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+    /// How we used to work out part 2, trying every level to remove.
+    fn dampener_fixes_brute_force(rules: &SafetyRules, nums: &[usize]) -> Vec<usize> {
+        (0..nums.len())
+            .filter(|&i| {
+                let mut new_nums: Vec<usize> = nums[0..i].to_vec();
+                new_nums.extend(&nums[i + 1..]);
+                rules.is_safe(&new_nums)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sample_part2() {
+        assert_eq!(Day02::part2(&Day02::parse(SAMPLE).unwrap()).unwrap(), 4);
+    }
+
+    /// Whether removing some set of at most `k` levels makes the report safe, trying every one.
+    fn is_safe_with_removals_brute_force(rules: &SafetyRules, nums: &[usize], k: usize) -> bool {
+        (0_u32..1 << nums.len())
+            .filter(|removed| removed.count_ones() as usize <= k)
+            .any(|removed| {
+                let kept: Vec<usize> = (0..nums.len())
+                    .filter(|i| removed & (1 << i) == 0)
+                    .map(|i| nums[i])
+                    .collect();
+                rules.is_safe(&kept)
+            })
+    }
+
+    /// The puzzle's rules, along with some which are stricter or more lenient in every way.
+    fn some_rules() -> [SafetyRules; 4] {
+        let puzzle = SafetyRules::default();
+        [
+            puzzle,
+            SafetyRules {
+                direction: Direction::Increasing,
+                plateaus: true,
+                ..puzzle
+            },
+            SafetyRules {
+                min_step: 2,
+                direction: Direction::Decreasing,
+                ..puzzle
+            },
+            SafetyRules {
+                max_step: 4,
+                direction: Direction::None,
+                ..puzzle
+            },
+        ]
+    }
+
+    /// Reports which are mostly safe steps with the occasional bad one, so that plenty of them
+    /// need the dampener, and some need it more than once.
+    fn random_report(rng: &mut StdRng) -> Vec<usize> {
+        let increasing = rng.random_bool(0.5);
+        let mut level: i32 = rng.random_range(0..20);
+        let mut nums: Vec<usize> = Vec::new();
+        for _ in 0..rng.random_range(1..10) {
+            nums.push(level as usize);
+            let step = if rng.random_bool(0.8) {
+                rng.random_range(1..=3)
+            } else {
+                rng.random_range(-4..=4)
+            };
+            level = (level + if increasing { step } else { -step }).max(0);
+        }
+        nums
+    }
+
+    #[test]
+    fn removals_match_brute_force() {
+        let mut rng = StdRng::seed_from_u64(21);
+        for _ in 0..1000 {
+            let nums = random_report(&mut rng);
+            for rules in some_rules() {
+                for k in 0..4 {
+                    assert_eq!(
+                        rules.is_safe_with_removals(&nums, k),
+                        is_safe_with_removals_brute_force(&rules, &nums, k),
+                        "{nums:?} {k} {rules}"
+                    );
+                }
+            }
+            assert_eq!(
+                is_safe_with_removals(&nums, 0),
+                SafetyRules::default().is_safe(&nums)
+            );
+        }
+    }
+
+    #[test]
+    fn dampener_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..2000 {
+            let nums = random_report(&mut rng);
+            for rules in some_rules() {
+                let fixes = dampener_fixes_brute_force(&rules, &nums);
+                assert_eq!(
+                    rules.is_safe_dampened(&nums),
+                    !fixes.is_empty(),
+                    "{nums:?} {rules}"
+                );
+                assert_eq!(rules.dampener_fixes(&nums), fixes, "{nums:?} {rules}");
+            }
+        }
+    }
+}
@@ -1,170 +1,146 @@
-fn is_safe(nums: &[usize]) -> usize {
-    // If they're not increasing or decreasing, then do an early return.
-    if nums[0] == nums[1] {
-        return 0;
-    }
-    // Determine the direction.
-    let increasing = nums[0] < nums[1];
-    // For each window, check if it meets the requirements.
-    for window in nums.windows(2) {
-        let (a, b) = (window[0], window[1]);
-        if ((a < b) != increasing) || (a.abs_diff(b) < 1) || (a.abs_diff(b) > 3) {
-            return 0;
-        }
-    }
-    1
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc_core::{InputArgs, Solution};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use day02::{Day02, Direction, Reports, RulesError, SafetyRules};
+
+/// Solves day 2 of Advent of Code 2024.
+#[derive(Parser)]
+#[command(name = "day02", args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    rules: RulesArgs,
+    /// How many levels the Problem Dampener can remove from each report in part 2.
+    #[arg(long, value_name = "K", default_value_t = 1)]
+    tolerance: usize,
+    /// Instead of the answers, say for every report whether it's safe, which rule it first breaks
+    /// if it isn't, and which levels the Problem Dampener could remove to fix it.
+    #[arg(long, conflicts_with = "tolerance")]
+    report: bool,
 }
 
-fn part1(lines: impl Iterator<Item = String>) -> String {
-    lines
-        .map(|line| {
-            line.split_whitespace()
-                .map(|s| s.parse().expect("We know AoC input is good"))
-                .collect::<Vec<usize>>()
-        })
-        .map(|nums| is_safe(&nums))
-        .sum::<usize>()
-        .to_string()
+#[derive(Subcommand)]
+enum Command {
+    /// Show a histogram of the step sizes and the report lengths, how many reports go in each
+    /// direction, and how many first break each rule.
+    Stats {
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        rules: RulesArgs,
+        /// Print the statistics as text, or as CSV with one row for each number.
+        #[arg(long, value_enum, default_value_t = StatsFormat::Text)]
+        format: StatsFormat,
+    },
 }
 
-// This doesn't work :( Instead, I just brute-force it.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum StatsFormat {
+    Text,
+    Csv,
+}
 
-// fn part2(lines: impl Iterator<Item = String>) -> String {
-//     lines
-//         .map(|line| {
-//             line.split_whitespace()
-//                 .map(|s| s.parse().expect("We know AoC input is good"))
-//                 .collect::<Vec<usize>>()
-//         })
-//         .map(|nums| {
-//             // If more than one of the first five numbers aren't increasing or decreasing, do an
-//             // early return.
-//             let first_four_equal: [bool; 4] = [
-//                 nums[0] == nums[1],
-//                 nums[1] == nums[2],
-//                 nums[2] == nums[3],
-//                 nums[3] == nums[4],
-//             ];
-//             if first_four_equal.iter().filter(|b| **b == true).count() > 1 {
-//                 println!("Unsafe (more than one equal): {:?}", nums);
-//                 return 0;
-//             }
-//             // Determine the direction by checking the first four directions. We are lucky that our
-//             // input is always at least 5 numbers long.
-//             let first_four_directions: [bool; 4] = [
-//                 nums[0] < nums[1],
-//                 nums[1] < nums[2],
-//                 nums[2] < nums[3],
-//                 nums[3] < nums[4],
-//             ];
-//             // If we have different directions two times, then we know we need to remove more than
-//             // one unsafe level, which we aren't allowed to do.
-//             if first_four_directions
-//                 .iter()
-//                 .filter(|b| **b == false)
-//                 .count()
-//                 == 2
-//             {
-//                 println!("Unsafe (different directions): {:?}", nums);
-//                 return 0;
-//             }
-//             // Finally, our direction will be the most common one.
-//             let increasing: bool = first_four_directions
-//                 .into_iter()
-//                 .max()
-//                 .expect("We know we have good data here.");
-//
-//             // Keep track of whether we've already skipped.
-//             let mut skipped_before = false;
-//             // If we've just skipped, the lower index will be one lower than usual.
-//             let mut low_i: usize = 0;
-//
-//             for i in 1..nums.len() {
-//                 let i_valid = ((nums[low_i] < nums[i]) == increasing)
-//                     && (nums[low_i].abs_diff(nums[i]) >= 1)
-//                     && (nums[low_i].abs_diff(nums[i]) <= 3);
-//                 if i_valid {
-//                     // Increment the low_i as usual.
-//                     low_i = i;
-//                     continue;
-//                 }
-//                 // If we've already skipped one, we have two or more unsafe levels, so return 0.
-//                 if skipped_before {
-//                     println!("Unsafe (skipped before): {:?}", nums);
-//                     return 0;
-//                 }
-//                 // If we are at the end of the list and haven't skipped before, we can skip the last
-//                 // one and thus make the reading safe!
-//                 if i == nums.len() - 1 {
-//                     return 1;
-//                 }
-//                 // With that check, we know that i + 1 will be in bounds for the nums vec.
-//                 if ((nums[low_i] < nums[i + 1]) == increasing)
-//                     && (nums[low_i].abs_diff(nums[i + 1]) >= 1)
-//                     && (nums[low_i].abs_diff(nums[i + 1]) <= 3)
-//                 {
-//                     // If skipping will yield a safe level, then we skip.
-//                     skipped_before = true;
-//                     // Change the low i so we actually skip in the comparisons.
-//                     low_i = i - 1;
-//                 } else {
-//                     // Otherwise, we'll return 0 because skipping doesn't help.
-//                     println!("Unsafe (skipping doesn't help): {:?}", nums);
-//                     return 0;
-//                 }
-//             }
-//             return 1;
-//         })
-//         .sum::<usize>()
-//         .to_string()
-// }
+/// The options which change the safety rules.
+#[derive(Args)]
+struct RulesArgs {
+    /// Read the safety rules from a TOML file. The other rule options override what's in it.
+    #[arg(long, value_name = "FILE")]
+    rules: Option<PathBuf>,
+    /// The smallest safe step between two levels. [default: 1]
+    #[arg(long, value_name = "N")]
+    min_step: Option<usize>,
+    /// The largest safe step between two levels. [default: 3]
+    #[arg(long, value_name = "N")]
+    max_step: Option<usize>,
+    /// Which way the levels of a safe report go. [default: either]
+    #[arg(long, value_enum)]
+    direction: Option<Direction>,
+    /// Whether two levels in a row can be the same. [default: false]
+    #[arg(
+        long,
+        value_name = "BOOL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true"
+    )]
+    plateaus: Option<bool>,
+}
 
-fn part2(lines: impl Iterator<Item = String>) -> String {
-    lines
-        .map(|line| {
-            line.split_whitespace()
-                .map(|s| s.parse().expect("We know AoC input is good"))
-                .collect::<Vec<usize>>()
-        })
-        .map(|nums| {
-            let mut options: Vec<Vec<usize>> = Vec::new();
-            for i in 0..nums.len() {
-                let mut new_nums: Vec<usize> = nums[0..i].to_vec();
-                new_nums.extend(&nums[i + 1..]);
-                options.push(new_nums);
-            }
-            options
-                .into_iter()
-                .map(|v| is_safe(&v))
-                .max()
-                .expect("We know we have at least one.")
-        })
-        .sum::<usize>()
-        .to_string()
+impl RulesArgs {
+    /// The rules from the file if there is one, or the puzzle's if not, with any other options on
+    /// top.
+    fn safety_rules(&self) -> Result<SafetyRules, RulesError> {
+        let mut rules = match &self.rules {
+            Some(path) => SafetyRules::load(path)?,
+            None => SafetyRules::default(),
+        };
+        rules.min_step = self.min_step.unwrap_or(rules.min_step);
+        rules.max_step = self.max_step.unwrap_or(rules.max_step);
+        rules.direction = self.direction.unwrap_or(rules.direction);
+        rules.plateaus = self.plateaus.unwrap_or(rules.plateaus);
+        rules.validate()
+    }
 }
 
-fn main() -> std::io::Result<()> {
-    let lines = std::io::stdin()
-        .lines()
-        .collect::<std::io::Result<Vec<String>>>()?;
-    println!("Part 1: {}", part1(lines.clone().into_iter()));
-    println!("Part 2: {}", part2(lines.into_iter()));
-    Ok(())
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let (input, rules) = match &cli.command {
+        Some(Command::Stats { input, rules, .. }) => (input, rules),
+        None => (&cli.input, &cli.rules),
+    };
+    let rules = match rules.safety_rules() {
+        Ok(rules) => rules,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    aoc_core::for_each_input(Day02::DAY, input, |source, input| {
+        let reports = match Day02::parse(input) {
+            Ok(reports) => reports,
+            Err(e) => {
+                eprintln!("error: {source}: {}", e.diagnostic(input));
+                return false;
+            }
+        };
+        match cli.command {
+            Some(Command::Stats { format, .. }) => print_stats(&reports, &rules, format),
+            None if cli.report => print_report(&reports, &rules),
+            None => print_answers(&reports, &rules, cli.tolerance),
+        }
+        true
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn print_answers(reports: &Reports, rules: &SafetyRules, tolerance: usize) {
+    let safe = reports
+        .iter()
+        .filter(|report| rules.is_safe(report.levels()))
+        .count();
+    println!("Part 1: {safe}");
+    let dampened = reports
+        .iter()
+        .filter(|report| rules.is_safe_with_removals(report.levels(), tolerance))
+        .count();
+    println!("Part 2: {dampened}");
+}
 
-    const SAMPLE: &str = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
+fn print_report(reports: &Reports, rules: &SafetyRules) {
+    for (i, report) in reports.iter().enumerate() {
+        println!("line {}: {}", i + 1, rules.diagnose(report.levels()));
+    }
+    println!("\n{}", reports.summary(rules));
+}
 
-    #[test]
-    fn sample_part2() {
-        assert_eq!(part2(SAMPLE.lines().map(ToOwned::to_owned)), "4");
+fn print_stats(reports: &Reports, rules: &SafetyRules, format: StatsFormat) {
+    let stats = reports.stats(rules);
+    match format {
+        StatsFormat::Text => print!("{stats}"),
+        StatsFormat::Csv => print!("{}", stats.to_csv()),
     }
 }
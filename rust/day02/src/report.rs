@@ -0,0 +1,232 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use aoc_core::{parse_token, ParseError};
+
+use crate::SafetyRules;
+
+/// One report from the reactor: a line of levels.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Report {
+    levels: Vec<usize>,
+}
+
+impl Report {
+    /// # Panics
+    ///
+    /// If there are no levels, since a report always has at least one.
+    pub fn new(levels: Vec<usize>) -> Self {
+        assert!(
+            !levels.is_empty(),
+            "a report should have at least one level"
+        );
+        Self { levels }
+    }
+
+    pub fn levels(&self) -> &[usize] {
+        &self.levels
+    }
+
+    /// Whether the report is safe by the puzzle's rules. Use [`SafetyRules::is_safe`] for any
+    /// others.
+    pub fn is_safe(&self) -> bool {
+        SafetyRules::default().is_safe(&self.levels)
+    }
+
+    /// Whether the report is safe by the puzzle's rules once the Problem Dampener has had a go at
+    /// it, and which level it removed if it had to.
+    pub fn dampened_safety(&self) -> Safety {
+        let rules = SafetyRules::default();
+        if rules.is_safe(&self.levels) {
+            return Safety::Safe;
+        }
+        match rules.dampener_fixes(&self.levels).first() {
+            Some(&i) => Safety::FixedByRemoving(i),
+            None => Safety::Unsafe,
+        }
+    }
+
+    /// Parse the `i`th line of the input.
+    fn parse_line(i: usize, line: &str) -> Result<Self, ParseError> {
+        let levels = line
+            .split_whitespace()
+            .map(|s| parse_token(i, line, s, "a level"))
+            .collect::<Result<Vec<usize>, _>>()?;
+        if levels.is_empty() {
+            return Err(ParseError::at_end_of_line(i, line, "a level"));
+        }
+        Ok(Self::new(levels))
+    }
+}
+
+impl FromStr for Report {
+    type Err = ParseError;
+
+    /// Parse a report from one line of levels separated by whitespace.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        Self::parse_line(0, line)
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let levels: Vec<String> = self.levels.iter().map(usize::to_string).collect();
+        write!(f, "{}", levels.join(" "))
+    }
+}
+
+/// How safe a report is with the Problem Dampener, from [`Report::dampened_safety`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Safety {
+    /// The report is safe without removing anything.
+    Safe,
+    /// The report is safe once the level at this index has been removed. If more than one level
+    /// would do, this is the first.
+    FixedByRemoving(usize),
+    /// The report isn't safe even with a level removed.
+    Unsafe,
+}
+
+/// Every report in the puzzle input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reports {
+    reports: Vec<Report>,
+}
+
+impl Reports {
+    pub fn new(reports: Vec<Report>) -> Self {
+        Self { reports }
+    }
+
+    pub fn reports(&self) -> &[Report] {
+        &self.reports
+    }
+
+    pub fn len(&self) -> usize {
+        self.reports.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reports.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Report> {
+        self.reports.iter()
+    }
+
+    /// How many of the reports are safe by some rules, with and without the Problem Dampener.
+    pub fn summary(&self, rules: &SafetyRules) -> Summary {
+        let mut summary = Summary {
+            reports: self.len(),
+            ..Summary::default()
+        };
+        for report in self {
+            summary.levels += report.levels().len();
+            if rules.is_safe(report.levels()) {
+                summary.safe += 1;
+            } else if rules.is_safe_dampened(report.levels()) {
+                summary.dampened += 1;
+            }
+        }
+        summary
+    }
+}
+
+impl<'a> IntoIterator for &'a Reports {
+    type Item = &'a Report;
+    type IntoIter = std::slice::Iter<'a, Report>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromStr for Reports {
+    type Err = ParseError;
+
+    /// Parse one report from each line.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| Report::parse_line(i, line))
+            .collect::<Result<_, _>>()
+            .map(Self::new)
+    }
+}
+
+/// How many reports are safe, from [`Reports::summary`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub reports: usize,
+    /// The total number of levels in all of the reports.
+    pub levels: usize,
+    pub safe: usize,
+    /// The number of reports which aren't safe, but are once the Problem Dampener has removed a
+    /// level.
+    pub dampened: usize,
+}
+
+impl Summary {
+    /// The number of reports which aren't safe even with the Problem Dampener.
+    pub fn unsafe_reports(&self) -> usize {
+        self.reports - self.safe - self.dampened
+    }
+
+    /// The average number of levels in a report, or [`Option::None`] if there aren't any reports.
+    pub fn mean_length(&self) -> Option<f64> {
+        (self.reports > 0).then(|| self.levels as f64 / self.reports as f64)
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} reports: {} safe, {} safe with the Problem Dampener, {} unsafe",
+            self.reports,
+            self.safe,
+            self.dampened,
+            self.unsafe_reports()
+        )?;
+        if let Some(mean) = self.mean_length() {
+            write!(f, " ({mean:.1} levels on average)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports() {
+        let reports: Reports = include_str!("../../samples/day02.txt").parse().unwrap();
+        let safety: Vec<(bool, Safety)> = reports
+            .iter()
+            .map(|report| (report.is_safe(), report.dampened_safety()))
+            .collect();
+        assert_eq!(
+            safety,
+            [
+                (true, Safety::Safe),
+                (false, Safety::Unsafe),
+                (false, Safety::Unsafe),
+                (false, Safety::FixedByRemoving(1)),
+                (false, Safety::FixedByRemoving(2)),
+                (true, Safety::Safe),
+            ]
+        );
+        let summary = reports.summary(&SafetyRules::default());
+        assert_eq!(
+            summary.to_string(),
+            "6 reports: 2 safe, 2 safe with the Problem Dampener, 2 unsafe (5.0 levels on average)"
+        );
+
+        assert_eq!("1 3  2".parse::<Report>().unwrap().to_string(), "1 3 2");
+        let error = "1 2\n3 x\n".parse::<Reports>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!("".parse::<Report>().unwrap_err().line, 1);
+    }
+}
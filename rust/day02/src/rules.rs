@@ -0,0 +1,366 @@
+//! The rules which decide whether a report is safe.
+//!
+//! The puzzle's rules are that the levels go in one direction, by between 1 and 3 at a time, but
+//! different reactors can be more or less tolerant, so every part of that can be changed. Rules
+//! can be written in a small TOML file, where anything left out is the same as in the puzzle:
+//!
+//! ```toml
+//! min_step = 1
+//! max_step = 5
+//! direction = "increasing" # or "decreasing", "either" or "none"
+//! plateaus = true
+//! ```
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+/// Which way the levels of a report have to go.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Every step goes up.
+    Increasing,
+    /// Every step goes down.
+    Decreasing,
+    /// Every step goes up, or every step goes down.
+    #[default]
+    Either,
+    /// Steps can go up and down in the same report.
+    None,
+}
+
+impl Direction {
+    /// Every way a report could go and still be safe. A report is safe if every step is safe
+    /// going one of these ways.
+    pub(crate) fn headings(self) -> &'static [Heading] {
+        match self {
+            Direction::Increasing => &[Heading::Up],
+            Direction::Decreasing => &[Heading::Down],
+            Direction::Either => &[Heading::Up, Heading::Down],
+            Direction::None => &[Heading::Anywhere],
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Increasing => write!(f, "increasing"),
+            Direction::Decreasing => write!(f, "decreasing"),
+            Direction::Either => write!(f, "either"),
+            Direction::None => write!(f, "none"),
+        }
+    }
+}
+
+/// Which way a report is going, once we've picked one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Heading {
+    Up,
+    Down,
+    Anywhere,
+}
+
+/// One of the [`SafetyRules`], which a step from one level to the next can break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// The two levels are the same, and plateaus aren't allowed.
+    Plateau,
+    /// The step goes the wrong way, for a report which should be increasing if `increasing`, or
+    /// decreasing if not.
+    Direction { increasing: bool },
+    /// The step is smaller than the smallest safe step, `min`.
+    MinStep { step: usize, min: usize },
+    /// The step is bigger than the largest safe step, `max`.
+    MaxStep { step: usize, max: usize },
+}
+
+impl Rule {
+    /// The name of every rule, in the same order as [`Rule`].
+    pub const NAMES: [&'static str; 4] = ["plateau", "direction", "min-step", "max-step"];
+
+    /// The name of the rule, which is the same however it was broken.
+    pub fn name(&self) -> &'static str {
+        Self::NAMES[self.index()]
+    }
+
+    /// Where the rule is in [`Rule::NAMES`].
+    pub(crate) fn index(&self) -> usize {
+        match self {
+            Rule::Plateau => 0,
+            Rule::Direction { .. } => 1,
+            Rule::MinStep { .. } => 2,
+            Rule::MaxStep { .. } => 3,
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rule::Plateau => write!(f, "the level stays the same"),
+            Rule::Direction { increasing: true } => {
+                write!(f, "it goes down in an increasing report")
+            }
+            Rule::Direction { increasing: false } => {
+                write!(f, "it goes up in a decreasing report")
+            }
+            Rule::MinStep { step, min } => {
+                write!(f, "a step of {step} is less than the smallest of {min}")
+            }
+            Rule::MaxStep { step, max } => {
+                write!(f, "a step of {step} is more than the largest of {max}")
+            }
+        }
+    }
+}
+
+/// What makes a report safe. The [`Default`] is the puzzle's rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SafetyRules {
+    /// The smallest a step between two levels can be, not counting plateaus.
+    pub min_step: usize,
+    /// The largest a step between two levels can be.
+    pub max_step: usize,
+    pub direction: Direction,
+    /// Whether two levels in a row can be the same. These don't count as going either way.
+    pub plateaus: bool,
+}
+
+impl Default for SafetyRules {
+    fn default() -> Self {
+        Self {
+            min_step: 1,
+            max_step: 3,
+            direction: Direction::Either,
+            plateaus: false,
+        }
+    }
+}
+
+impl SafetyRules {
+    /// Read the rules from a TOML file.
+    pub fn load(path: &Path) -> Result<Self, RulesError> {
+        let text = std::fs::read_to_string(path).map_err(|e| RulesError::Io(path.to_owned(), e))?;
+        text.parse()
+    }
+
+    /// Check that the rules make sense, which they don't if no step could ever be safe.
+    pub fn validate(self) -> Result<Self, RulesError> {
+        if self.min_step == 0 || self.min_step > self.max_step {
+            return Err(RulesError::Steps {
+                min: self.min_step,
+                max: self.max_step,
+            });
+        }
+        Ok(self)
+    }
+
+    /// Whether going from `a` to `b` is a safe step, going the given way.
+    fn is_safe_step(&self, a: usize, b: usize, heading: Heading) -> bool {
+        self.broken_rule(a, b, heading).is_none()
+    }
+
+    /// The rule which going from `a` to `b` breaks, going the given way, if it breaks any.
+    pub(crate) fn broken_rule(&self, a: usize, b: usize, heading: Heading) -> Option<Rule> {
+        let step = a.abs_diff(b);
+        if step == 0 {
+            return (!self.plateaus).then_some(Rule::Plateau);
+        }
+        match heading {
+            Heading::Up if a > b => Some(Rule::Direction { increasing: true }),
+            Heading::Down if a < b => Some(Rule::Direction { increasing: false }),
+            _ if step < self.min_step => Some(Rule::MinStep {
+                step,
+                min: self.min_step,
+            }),
+            _ if step > self.max_step => Some(Rule::MaxStep {
+                step,
+                max: self.max_step,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn is_safe(&self, nums: &[usize]) -> bool {
+        self.direction.headings().iter().any(|&heading| {
+            nums.windows(2)
+                .all(|w| self.is_safe_step(w[0], w[1], heading))
+        })
+    }
+
+    /// Whether a report is safe once the Problem Dampener has removed at most one level from it.
+    pub fn is_safe_dampened(&self, nums: &[usize]) -> bool {
+        self.is_safe(nums)
+            || self
+                .direction
+                .headings()
+                .iter()
+                .any(|&heading| !self.dampener_fixes_towards(nums, heading).is_empty())
+    }
+
+    /// Every level which the Problem Dampener could remove to make the report safe, in order.
+    pub fn dampener_fixes(&self, nums: &[usize]) -> Vec<usize> {
+        let mut fixes: Vec<usize> = self
+            .direction
+            .headings()
+            .iter()
+            .flat_map(|&heading| self.dampener_fixes_towards(nums, heading))
+            .collect();
+        fixes.sort_unstable();
+        fixes.dedup();
+        fixes
+    }
+
+    /// Every level which could be removed to make the report safe going the given way.
+    ///
+    /// Rather than trying every level to remove, we work out which parts of the report are safe
+    /// on their own first, so that each level can be checked in constant time.
+    fn dampener_fixes_towards(&self, nums: &[usize], heading: Heading) -> Vec<usize> {
+        let n = nums.len();
+        // Removing a level from these leaves at most one, which is always safe.
+        if n < 3 {
+            return (0..n).collect();
+        }
+        let step = |i: usize, j: usize| self.is_safe_step(nums[i], nums[j], heading);
+        // `before[i]` is whether the levels before `i` are safe by themselves, and `after[i]`
+        // whether the levels after `i` are.
+        let mut before = vec![true; n];
+        for i in 2..n {
+            before[i] = before[i - 1] && step(i - 2, i - 1);
+        }
+        let mut after = vec![true; n];
+        for i in (0..n - 2).rev() {
+            after[i] = after[i + 1] && step(i + 1, i + 2);
+        }
+        // Removing level `i` also means the levels either side of it have to make a safe step.
+        (0..n)
+            .filter(|&i| before[i] && after[i] && (i == 0 || i == n - 1 || step(i - 1, i + 1)))
+            .collect()
+    }
+
+    /// Whether a report can be made safe by removing at most `k` levels from it.
+    ///
+    /// Removing levels leaves a subsequence of the report, so this is the same as asking whether
+    /// the longest subsequence which is safe has at least `nums.len() - k` levels. We find that
+    /// for each way the report could go with the usual quadratic longest-chain DP, where
+    /// `longest[j]` is the length of the longest safe subsequence ending with level `j`. Removing
+    /// none or one level has quicker ways of its own.
+    pub fn is_safe_with_removals(&self, nums: &[usize], k: usize) -> bool {
+        match k {
+            0 => return self.is_safe(nums),
+            1 => return self.is_safe_dampened(nums),
+            _ if nums.len() <= k + 1 => return true,
+            _ => (),
+        }
+        self.direction.headings().iter().any(|&heading| {
+            let mut longest = vec![1; nums.len()];
+            for j in 1..nums.len() {
+                longest[j] = (0..j)
+                    .filter(|&i| self.is_safe_step(nums[i], nums[j], heading))
+                    .map(|i| longest[i] + 1)
+                    .max()
+                    .unwrap_or(1);
+            }
+            longest.into_iter().max().unwrap_or(0) + k >= nums.len()
+        })
+    }
+}
+
+impl FromStr for SafetyRules {
+    type Err = RulesError;
+
+    /// Parse the rules from TOML, like in a file for [`SafetyRules::load`].
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let rules: SafetyRules = toml::from_str(text).map_err(RulesError::Toml)?;
+        rules.validate()
+    }
+}
+
+impl Display for SafetyRules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "steps of {} to {}, direction {}, ",
+            self.min_step, self.max_step, self.direction
+        )?;
+        if self.plateaus {
+            write!(f, "plateaus allowed")
+        } else {
+            write!(f, "no plateaus")
+        }
+    }
+}
+
+/// A problem with a set of [`SafetyRules`].
+#[derive(Debug)]
+pub enum RulesError {
+    Io(PathBuf, std::io::Error),
+    Toml(toml::de::Error),
+    /// The step range is empty, or starts at zero, which is what plateaus are for.
+    Steps {
+        min: usize,
+        max: usize,
+    },
+}
+
+impl Display for RulesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RulesError::Io(path, e) => write!(f, "could not read {}: {e}", path.display()),
+            RulesError::Toml(e) => write!(f, "could not parse the rules: {e}"),
+            RulesError::Steps { min, max } => write!(
+                f,
+                "steps of {min} to {max} don't make sense: the smallest step has to be at least 1 \
+                and no bigger than the largest"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RulesError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_rules() {
+        let rules: SafetyRules = "max_step = 5\ndirection = \"increasing\"\nplateaus = true"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            rules,
+            SafetyRules {
+                min_step: 1,
+                max_step: 5,
+                direction: Direction::Increasing,
+                plateaus: true,
+            }
+        );
+        assert!(rules.is_safe(&[1, 1, 6, 7]));
+        assert!(!rules.is_safe(&[7, 6, 1]));
+        assert!(rules.is_safe_dampened(&[1, 1, 7, 6]));
+
+        let rules = SafetyRules {
+            direction: Direction::None,
+            ..SafetyRules::default()
+        };
+        assert!(rules.is_safe(&[1, 3, 2, 5]));
+        assert!(!rules.is_safe(&[1, 3, 3, 5]));
+        assert!(rules.is_safe_with_removals(&[1, 3, 3, 9, 5], 2));
+
+        assert!(matches!(
+            "min_step = 4".parse::<SafetyRules>(),
+            Err(RulesError::Steps { min: 4, max: 3 })
+        ));
+        assert!(matches!(
+            "steps = 4".parse::<SafetyRules>(),
+            Err(RulesError::Toml(_))
+        ));
+    }
+}
@@ -0,0 +1,210 @@
+//! Statistics about a set of reports, for tuning the [`SafetyRules`].
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use crate::{Reports, Rule, SafetyRules};
+
+/// How the levels of the reports tend to change, from [`Reports::stats`].
+///
+/// It's shown as text with [`Display`], or as CSV with [`Stats::to_csv`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub reports: usize,
+    /// The number of reports where every step goes up.
+    pub increasing: usize,
+    /// The number of reports where every step goes down.
+    pub decreasing: usize,
+    /// How many steps there are of each size, over every pair of levels next to each other in
+    /// every report.
+    pub steps: BTreeMap<usize, usize>,
+    /// How many reports there are with each number of levels.
+    pub lengths: BTreeMap<usize, usize>,
+    /// How many reports first break each rule, in the order of [`Rule::NAMES`].
+    pub failures: [usize; 4],
+}
+
+impl Stats {
+    /// The number of reports which aren't increasing or decreasing, because they change
+    /// direction, have a plateau, or only have one level.
+    pub fn neither(&self) -> usize {
+        self.reports - self.increasing - self.decreasing
+    }
+
+    /// The number of reports of each direction, along with its name.
+    fn directions(&self) -> [(&'static str, usize); 3] {
+        [
+            ("increasing", self.increasing),
+            ("decreasing", self.decreasing),
+            ("neither", self.neither()),
+        ]
+    }
+
+    /// The statistics as CSV, with one row for each number in them.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("statistic,value,count\n");
+        for (direction, count) in self.directions() {
+            csv.push_str(&format!("direction,{direction},{count}\n"));
+        }
+        for (step, count) in &self.steps {
+            csv.push_str(&format!("step,{step},{count}\n"));
+        }
+        for (length, count) in &self.lengths {
+            csv.push_str(&format!("length,{length},{count}\n"));
+        }
+        for (rule, count) in Rule::NAMES.iter().zip(self.failures) {
+            csv.push_str(&format!("failure,{rule},{count}\n"));
+        }
+        csv
+    }
+}
+
+impl Reports {
+    /// Count the steps, lengths and directions of the reports, and which rules they break.
+    pub fn stats(&self, rules: &SafetyRules) -> Stats {
+        let mut stats = Stats {
+            reports: self.len(),
+            increasing: 0,
+            decreasing: 0,
+            steps: BTreeMap::new(),
+            lengths: BTreeMap::new(),
+            failures: [0; 4],
+        };
+        for report in self {
+            let levels = report.levels();
+            *stats.lengths.entry(levels.len()).or_default() += 1;
+            for w in levels.windows(2) {
+                *stats.steps.entry(w[0].abs_diff(w[1])).or_default() += 1;
+            }
+            if levels.len() > 1 {
+                if levels.windows(2).all(|w| w[0] < w[1]) {
+                    stats.increasing += 1;
+                } else if levels.windows(2).all(|w| w[0] > w[1]) {
+                    stats.decreasing += 1;
+                }
+            }
+            if let Some(violation) = rules.diagnose(levels).violation {
+                stats.failures[violation.rule.index()] += 1;
+            }
+        }
+        stats
+    }
+}
+
+/// The widest a bar of a histogram gets.
+const BAR_WIDTH: usize = 40;
+
+/// Write a histogram with a bar for every value which has a count. Values with nothing in them are
+/// left out, since one huge step would otherwise mean a row for every number up to it.
+fn write_histogram(
+    f: &mut std::fmt::Formatter<'_>,
+    counts: &BTreeMap<usize, usize>,
+) -> std::fmt::Result {
+    let Some((&last, _)) = counts.last_key_value() else {
+        return writeln!(f, "  (none)");
+    };
+    let value_width = last.to_string().len();
+    let most = counts.values().copied().max().unwrap_or(0);
+    let count_width = most.to_string().len();
+    for (&value, &count) in counts {
+        // Anything at all gets at least a bit of a bar, so it doesn't look like nothing.
+        let bar = (count * BAR_WIDTH).div_ceil(most);
+        writeln!(
+            f,
+            "  {value:>value_width$}  {count:>count_width$}  {}",
+            "#".repeat(bar)
+        )?;
+    }
+    Ok(())
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Reports: {}", self.reports)?;
+        for (direction, count) in self.directions() {
+            let percent = if self.reports == 0 {
+                0.0
+            } else {
+                100.0 * count as f64 / self.reports as f64
+            };
+            writeln!(f, "  {direction:<10}  {count}  ({percent:.1}%)")?;
+        }
+        writeln!(f, "\nStep sizes:")?;
+        write_histogram(f, &self.steps)?;
+        writeln!(f, "\nReport lengths:")?;
+        write_histogram(f, &self.lengths)?;
+        writeln!(f, "\nFirst rule broken:")?;
+        for (rule, count) in Rule::NAMES.iter().zip(self.failures) {
+            writeln!(f, "  {rule:<9}  {count}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample() {
+        let reports: Reports = include_str!("../../samples/day02.txt").parse().unwrap();
+        let stats = reports.stats(&SafetyRules::default());
+        assert_eq!(
+            (stats.increasing, stats.decreasing, stats.neither()),
+            (2, 2, 2)
+        );
+        assert_eq!(
+            stats.steps,
+            BTreeMap::from([(0, 1), (1, 10), (2, 9), (3, 2), (4, 1), (5, 1)])
+        );
+        assert_eq!(stats.lengths, BTreeMap::from([(5, 6)]));
+        assert_eq!(stats.failures, [1, 1, 0, 2]);
+        assert_eq!(
+            stats.to_string(),
+            "Reports: 6
+  increasing  2  (33.3%)
+  decreasing  2  (33.3%)
+  neither     2  (33.3%)
+
+Step sizes:
+  0   1  ####
+  1  10  ########################################
+  2   9  ####################################
+  3   2  ########
+  4   1  ####
+  5   1  ####
+
+Report lengths:
+  5  6  ########################################
+
+First rule broken:
+  plateau    1
+  direction  1
+  min-step   0
+  max-step   2
+"
+        );
+        assert!(stats
+            .to_csv()
+            .starts_with("statistic,value,count\ndirection,increasing,2\n"));
+        assert!(stats.to_csv().ends_with("failure,max-step,2\n"));
+    }
+
+    #[test]
+    fn huge_step() {
+        let reports: Reports = "1 2 3\n1 1000000000000\n".parse().unwrap();
+        let stats = reports.stats(&SafetyRules::default());
+        assert_eq!(stats.steps, BTreeMap::from([(1, 2), (999_999_999_999, 1)]));
+        let text = stats.to_string();
+        let steps = text
+            .split("Step sizes:\n")
+            .nth(1)
+            .and_then(|rest| rest.split("\n\n").next())
+            .unwrap();
+        assert_eq!(
+            steps,
+            "             1  2  ########################################
+  999999999999  1  ####################"
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use aoc_core::bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day03::Day03;
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
+const SAMPLE: &str = include_str!("../../samples/day03.txt");
+
+/// Corrupted memory with instructions scattered between junk, like the real input but about ten
+/// times as long.
+fn generated_input() -> String {
+    const JUNK: [&str; 8] = ["@", "what()", "mul[", "+", "from(", ")", "mul(4*", "'"];
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut input = String::new();
+    while input.len() < 200_000 {
+        match rng.random_range(0..10) {
+            0..4 => {
+                let lhs: u32 = rng.random_range(1..1000);
+                let rhs: u32 = rng.random_range(1..1000);
+                input.push_str(&format!("mul({lhs},{rhs})"));
+            }
+            4 => input.push_str("do()"),
+            5 => input.push_str("don't()"),
+            _ => input.push_str(JUNK[rng.random_range(0..JUNK.len())]),
+        }
+    }
+    input
+}
+
+fn bench(c: &mut Criterion) {
+    bench_solution::<Day03>(c, "sample", SAMPLE);
+    bench_solution::<Day03>(c, "generated", &generated_input());
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
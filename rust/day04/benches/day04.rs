@@ -0,0 +1,28 @@
+use aoc_core::bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day04::Day04;
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
+const SAMPLE: &str = include_str!("../../samples/day04.txt");
+
+/// A word search of random `X`, `M`, `A` and `S`, twice as wide and tall as the real input.
+fn generated_input() -> String {
+    let mut rng = StdRng::seed_from_u64(4);
+    let mut input = String::new();
+    for _ in 0..280 {
+        for _ in 0..280 {
+            input.push(['X', 'M', 'A', 'S'][rng.random_range(0..4)]);
+        }
+        input.push('\n');
+    }
+    input
+}
+
+fn bench(c: &mut Criterion) {
+    bench_solution::<Day04>(c, "sample", SAMPLE);
+    bench_solution::<Day04>(c, "generated", &generated_input());
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
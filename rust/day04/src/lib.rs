@@ -0,0 +1,226 @@
+use aoc_core::{Answer, ParseError, Solution, SolveError};
+use aoc_grid::{Coord, Grid, Vector, EIGHT_DIRECTIONS};
+
+const XMAS: [char; 4] = ['X', 'M', 'A', 'S'];
+const MAS_CROSSES: [[(Vector, char); 5]; 4] = [
+    // -> M S
+    //     A
+    //    M S
+    [
+        ([0, 0], 'M'),
+        ([2, 0], 'S'),
+        ([1, 1], 'A'),
+        ([0, 2], 'M'),
+        ([2, 2], 'S'),
+    ],
+    //    S S
+    //     A
+    // -> M M
+    [
+        ([0, 0], 'M'),
+        ([0, -2], 'S'),
+        ([1, -1], 'A'),
+        ([2, 0], 'M'),
+        ([2, -2], 'S'),
+    ],
+    // S M
+    //  A
+    // S M <-
+    [
+        ([0, 0], 'M'),
+        ([-2, 0], 'S'),
+        ([-1, -1], 'A'),
+        ([0, -2], 'M'),
+        ([-2, -2], 'S'),
+    ],
+    // M M <-
+    //  A
+    // S S
+    [
+        ([0, 0], 'M'),
+        ([0, 2], 'S'),
+        ([-1, 1], 'A'),
+        ([-2, 0], 'M'),
+        ([-2, 2], 'S'),
+    ],
+];
+
+/// Checks if a word is at a position while going in one direction.
+fn is_word_at_pos_with_direction(
+    grid: &Grid<char>,
+    word: &[char],
+    position: usize,
+    direction: Vector,
+) -> bool {
+    let mut grid_index: usize = position;
+    let mut letter: usize = 0;
+    if word.is_empty() {
+        return false;
+    }
+    // While we still have unfound letters.
+    while letter < word.len() {
+        if grid[grid_index] != word[letter] {
+            return false;
+        }
+        if letter == word.len() - 1 {
+            return true;
+        }
+        if let Some(next_index) = grid.index_plus_vector(grid_index, direction) {
+            grid_index = next_index;
+        } else {
+            // If we went out of bounds, then we know for certain that the word isn't here.
+            return false;
+        }
+        letter += 1;
+    }
+    true
+}
+
+/// Finds the positions of the word and its multiplicity (how often it occurred at that
+/// position).
+fn find_positions(grid: &Grid<char>, word: &[char]) -> Vec<(Coord, usize)> {
+    let mut to_return: Vec<(usize, usize)> = Vec::new();
+    for i in 0..grid.len() {
+        if grid[i] == word[0] {
+            let multiplicity = EIGHT_DIRECTIONS
+                .iter()
+                .map(|direction| is_word_at_pos_with_direction(grid, word, i, *direction))
+                .filter(|b| *b)
+                .count();
+            if multiplicity > 0 {
+                to_return.push((i, multiplicity));
+            }
+        }
+    }
+    to_return
+        .into_iter()
+        .map(|index| (grid.get_coord(index.0), index.1))
+        .collect()
+}
+
+/// Finds occurrences of the given formation.
+///
+/// If one of the vectors will bring the position out-of-bounds, the function immediately
+/// returns false.
+///
+/// # Arguments
+///
+/// * `position` - the vectors will be applied with respect to this index.
+/// * `vectors_with_chars` - the vectors will be added to the position, then the character at
+///   this offset position will be compared with the character in the tuple.
+#[must_use]
+fn check_relative_positions(
+    grid: &Grid<char>,
+    position: usize,
+    vectors_with_chars: Vec<(Vector, char)>,
+) -> bool {
+    for (vector, c) in vectors_with_chars {
+        if let Some(index) = grid.index_plus_vector(position, vector) {
+            if grid[index] != c {
+                return false;
+            }
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+/// Finds MAS-crosses and their multiplicity (how many crosses each result has).
+fn find_mas_crosses(grid: &Grid<char>) -> Vec<(Coord, usize)> {
+    let mut to_return: Vec<(usize, usize)> = Vec::new();
+    for i in 0..grid.len() {
+        // Do an early return if we don't have an M.
+        if grid[i] != 'M' {
+            continue;
+        }
+        let mut multiplicity: usize = 0;
+        for mas_cross in MAS_CROSSES {
+            if check_relative_positions(grid, i, mas_cross.to_vec()) {
+                multiplicity += 1;
+            }
+        }
+        if multiplicity > 0 {
+            to_return.push((i, multiplicity));
+        }
+    }
+    to_return
+        .into_iter()
+        .map(|index| (grid.get_coord(index.0), index.1))
+        .collect()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<Answer, SolveError> {
+        let found_positions = find_positions(grid, XMAS.as_slice());
+        let count: usize = found_positions
+            .into_iter()
+            .map(|(_, multiplicity)| multiplicity)
+            .sum();
+        Ok(count.into())
+    }
+
+    fn part2(grid: &Self::Input) -> Result<Answer, SolveError> {
+        let mas_occurrences = find_mas_crosses(grid);
+        let count: usize = mas_occurrences
+            .into_iter()
+            .map(|(_, multiplicity)| multiplicity)
+            .sum();
+        Ok(count.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE0: &str = "..X...
+.SAMX.
+.A..A.
+XMAS.S
+.X....";
+    const SAMPLE1: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+    const SAMPLE2: &str = ".M.S......
+..A..MSMS.
+.M.S.MAA..
+..A.ASMSM.
+.M.S.M....
+..........
+S.S.S.S.S.
+.A.A.A.A..
+M.M.M.M.M.
+..........";
+
+    #[test]
+    fn sample_part1_0() {
+        assert_eq!(Day04::part1(&Day04::parse(SAMPLE0).unwrap()).unwrap(), 4);
+    }
+
+    #[test]
+    fn sample_part1() {
+        assert_eq!(Day04::part1(&Day04::parse(SAMPLE1).unwrap()).unwrap(), 18);
+    }
+
+    #[test]
+    fn sample_part2() {
+        assert_eq!(Day04::part2(&Day04::parse(SAMPLE2).unwrap()).unwrap(), 9);
+    }
+}
@@ -0,0 +1,46 @@
+use aoc_core::bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day05::Day05;
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{RngExt, SeedableRng};
+
+const SAMPLE: &str = include_str!("../../samples/day05.txt");
+
+/// A rule for every pair of 49 pages and 200 updates, which is the size of the real input.
+fn generated_input() -> String {
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut pages: Vec<u32> = (11..60).collect();
+    pages.shuffle(&mut rng);
+
+    let mut input = String::new();
+    for (i, before) in pages.iter().enumerate() {
+        for after in &pages[i + 1..] {
+            input.push_str(&format!("{before}|{after}\n"));
+        }
+    }
+    input.push('\n');
+    for _ in 0..200 {
+        let length = 2 * rng.random_range(2..12) + 1;
+        let update: Vec<String> = pages
+            .sample(&mut rng, length)
+            .map(ToString::to_string)
+            .collect();
+        input.push_str(&update.join(","));
+        input.push('\n');
+    }
+    input
+}
+
+fn bench(c: &mut Criterion) {
+    bench_solution::<Day05>(c, "sample", SAMPLE);
+    bench_solution::<Day05>(c, "generated", &generated_input());
+}
+
+// Part 2 takes around a second on the generated input.
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench
+}
+criterion_main!(benches);
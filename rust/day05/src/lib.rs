@@ -0,0 +1,218 @@
+use aoc_core::{parse_token, Answer, ParseError, Solution, SolveError};
+
+fn get_middle<A>(slice: &[A]) -> &A {
+    assert_eq!(slice.len() % 2, 1);
+    &slice[slice.len() / 2]
+}
+
+/// The page ordering rules, and then the updates.
+type PrintQueue = (Vec<[usize; 2]>, Vec<Vec<usize>>);
+
+fn parse_input(input: &str) -> Result<PrintQueue, ParseError> {
+    let mut lines = input.lines().enumerate();
+    let mut relations: Vec<[usize; 2]> = Vec::new();
+
+    // The rules come first, up until an empty line.
+    loop {
+        let Some((i, line)) = lines.next() else {
+            return Err(ParseError::at_end_of_input(
+                input,
+                "an empty line between the rules and the updates",
+            ));
+        };
+        if line.is_empty() {
+            break;
+        }
+        let Some((before, after)) = line.split_once('|') else {
+            return Err(ParseError::at_token(
+                i,
+                line,
+                line,
+                "a page ordering rule like `47|53`",
+            ));
+        };
+        relations.push([
+            parse_token(i, line, before, "a page number")?,
+            parse_token(i, line, after, "a page number")?,
+        ]);
+    }
+    let updates: Vec<Vec<usize>> = lines
+        .map(|(i, line)| {
+            let update: Vec<usize> = line
+                .split(',')
+                .map(|s| parse_token(i, line, s, "a page number"))
+                .collect::<Result<_, _>>()?;
+            // Both parts take the middle page, so there needs to be one.
+            if update.len().is_multiple_of(2) {
+                return Err(ParseError::at_token(
+                    i,
+                    line,
+                    line,
+                    "an odd number of pages",
+                ));
+            }
+            Ok(update)
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((relations, updates))
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = PrintQueue;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((relations, updates): &Self::Input) -> Result<Answer, SolveError> {
+        // We find all the correct updates...
+        let correct_updates: Vec<&Vec<usize>> = updates
+            .iter()
+            .filter(|l| {
+                // ... by filtering based on whether a swapped order appears in the relations vec.
+                // If it does, that implies that it violated an ordering rule.
+                for i in 0..l.len() {
+                    for n in &l[i..] {
+                        if relations.contains(&[*n, l[i]]) {
+                            return false;
+                        }
+                    }
+                }
+                true
+            })
+            .collect();
+
+        // We now find the middle value, sum them, and return that as our answer.
+        let sum: usize = correct_updates
+            .into_iter()
+            .map(|update| *get_middle(update.as_slice()))
+            .sum();
+        Ok(sum.into())
+    }
+
+    fn part2((relations, updates): &Self::Input) -> Result<Answer, SolveError> {
+        // We first find all incorrect updates by essentially doing the same as part 1 but swapping
+        // the filter function output.
+        let incorrect_updates: Vec<Vec<usize>> = updates
+            .iter()
+            .filter(|l| {
+                for i in 0..l.len() {
+                    for n in &l[i..] {
+                        if relations.contains(&[*n, l[i]]) {
+                            return true;
+                        }
+                    }
+                }
+                false
+            })
+            .cloned()
+            .collect();
+
+        // Then we correct the mistakes. We have a stack of all the incorrect updates. When we find
+        // a mistake, we'll swap the violating pair and re-add the update to the stack (because we
+        // may have introduced a new mistake). Eventually, after many swaps, we will have corrected
+        // all mistakes.
+        let mut to_correct_stack: Vec<Vec<usize>> = incorrect_updates;
+        let mut corrected_updates: Vec<Vec<usize>> = Vec::new();
+        while let Some(mut update) = to_correct_stack.pop() {
+            // We need this break_out variables for two reasons:
+            // 1. to break out of two for loops,
+            // 2. to tell if an update is now correct.
+            // It will be set to true if we swapped.
+            let mut break_out: bool = false;
+            for i in 0..update.len() {
+                for j in i..update.len() {
+                    if relations.contains(&[update[j], update[i]]) {
+                        update.swap(i, j);
+                        break_out = true;
+                    }
+                    if break_out {
+                        break;
+                    }
+                }
+                if break_out {
+                    break;
+                }
+            }
+            if break_out {
+                to_correct_stack.push(update);
+            } else {
+                corrected_updates.push(update);
+            }
+        }
+
+        // Finally, we get the middle value as before.
+        let sum: usize = corrected_updates
+            .into_iter()
+            .map(|update| *get_middle(update.as_slice()))
+            .sum();
+        Ok(sum.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE1: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+
+    #[test]
+    fn sample_part1() {
+        assert_eq!(Day05::part1(&Day05::parse(SAMPLE1).unwrap()).unwrap(), 143);
+    }
+
+    #[test]
+    fn sample_part2() {
+        assert_eq!(Day05::part2(&Day05::parse(SAMPLE1).unwrap()).unwrap(), 123);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Day05::parse("47|53\n97-13\n\n75,47,61\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        let error = Day05::parse("47|53\n\n75,4x,61\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 4));
+        let error = Day05::parse("47|53\n97|13\n").unwrap_err();
+        assert_eq!(error.line, 3);
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        let crlf = SAMPLE1.replace('\n', " \r\n");
+        let (input, changes) = aoc_core::normalise(&crlf);
+        assert_eq!(changes.carriage_returns, SAMPLE1.lines().count());
+        assert_eq!(Day05::part1(&Day05::parse(&input).unwrap()).unwrap(), 143);
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+
+use aoc_core::bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day06::Day06;
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
+const SAMPLE: &str = include_str!("../../samples/day06.txt");
+
+/// How many positions the guard visits before leaving the lab, or [`Option::None`] if they get
+/// stuck in a loop.
+fn walk_length(lab: &[Vec<bool>], start: [usize; 2]) -> Option<usize> {
+    let mut seen: HashSet<([usize; 2], usize)> = HashSet::new();
+    let mut visited: HashSet<[usize; 2]> = HashSet::new();
+    let [mut x, mut y] = start;
+    let mut direction: usize = 0;
+    loop {
+        if !seen.insert(([x, y], direction)) {
+            return None;
+        }
+        visited.insert([x, y]);
+        let [dx, dy] = [[0, -1], [1, 0], [0, 1], [-1, 0]][direction];
+        let (Some(next_x), Some(next_y)) = (x.checked_add_signed(dx), y.checked_add_signed(dy))
+        else {
+            return Some(visited.len());
+        };
+        match lab.get(next_y).and_then(|row| row.get(next_x)) {
+            None => return Some(visited.len()),
+            Some(true) => direction = (direction + 1) % 4,
+            Some(false) => [x, y] = [next_x, next_y],
+        }
+    }
+}
+
+/// A lab with sparse obstructions and the guard in the middle, where the guard visits between 300
+/// and 600 positions before leaving.
+///
+/// This is smaller than the real input (which is 130 by 130), because part 2 is far too slow to
+/// benchmark on anything that size.
+fn generated_input() -> String {
+    const SIZE: usize = 50;
+    let start = [SIZE / 2, SIZE / 2];
+    // Most random labs either let the guard walk straight out or trap them in a loop (which the
+    // real inputs never do), so keep trying until we get a reasonable walk.
+    for seed in 6.. {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let lab: Vec<Vec<bool>> = (0..SIZE)
+            .map(|y| {
+                (0..SIZE)
+                    .map(|x| [x, y] != start && rng.random_bool(0.04))
+                    .collect()
+            })
+            .collect();
+        if walk_length(&lab, start).is_some_and(|length| (300..=600).contains(&length)) {
+            let mut input = String::new();
+            for (y, row) in lab.iter().enumerate() {
+                for (x, &obstructed) in row.iter().enumerate() {
+                    input.push(match obstructed {
+                        _ if [x, y] == start => '^',
+                        true => '#',
+                        false => '.',
+                    });
+                }
+                input.push('\n');
+            }
+            return input;
+        }
+    }
+    unreachable!("We never run out of seeds.")
+}
+
+fn bench(c: &mut Criterion) {
+    bench_solution::<Day06>(c, "sample", SAMPLE);
+    bench_solution::<Day06>(c, "generated", &generated_input());
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench
+}
+criterion_main!(benches);
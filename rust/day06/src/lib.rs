@@ -0,0 +1,156 @@
+use aoc_core::{Answer, ParseError, Solution, SolveError};
+use aoc_grid::{Grid, Vector};
+
+fn quarter_turn_clockwise([x, y]: Vector) -> Vector {
+    [-y, x]
+}
+
+fn half_turn([x, y]: Vector) -> Vector {
+    [-x, -y]
+}
+
+fn mark_visited(grid: &mut Grid<char>) {
+    let mut guard_position: usize = grid
+        .find_index_of(&'^')
+        .expect("We checked for a guard when parsing.");
+    let mut vector: Vector = [0, -1];
+    grid[guard_position] = 'X';
+    while let Some(new_guard_position) = grid.index_plus_vector(guard_position, vector) {
+        if grid[new_guard_position] == '#' {
+            // We first need to take a step back.
+            guard_position = grid
+                .index_plus_vector(new_guard_position, half_turn(vector))
+                .unwrap();
+            // And then turn the vector.
+            vector = quarter_turn_clockwise(vector);
+        } else {
+            guard_position = new_guard_position;
+            grid[guard_position] = 'X';
+        }
+    }
+}
+
+fn has_loop(grid: &Grid<char>) -> bool {
+    let mut guard_position: usize = grid
+        .find_index_of(&'^')
+        .expect("We checked for a guard when parsing.");
+    let mut vector: Vector = [0, -1];
+    let mut positions_and_vectors: Vec<(usize, Vector)> = Vec::new();
+    while let Some(new_guard_position) = grid.index_plus_vector(guard_position, vector) {
+        if grid[new_guard_position] == '#' {
+            // We first need to take a step back.
+            guard_position = grid
+                .index_plus_vector(new_guard_position, half_turn(vector))
+                .unwrap();
+            // And then turn the vector.
+            vector = quarter_turn_clockwise(vector);
+        } else {
+            if positions_and_vectors.contains(&(new_guard_position, vector)) {
+                return true;
+            }
+            guard_position = new_guard_position;
+            positions_and_vectors.push((guard_position, vector));
+        }
+    }
+    false
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let grid = Grid::parse(input)?;
+        // Both parts need to start from exactly one guard, and rely on everything else being
+        // either empty or an obstruction.
+        let mut found_guard = false;
+        for (i, line) in input.lines().enumerate() {
+            for (offset, c) in line.char_indices() {
+                let token = &line[offset..offset + c.len_utf8()];
+                match c {
+                    '.' | '#' => {}
+                    '^' if !found_guard => found_guard = true,
+                    '^' => {
+                        let expected = "`.` or `#`, as there can only be one guard";
+                        return Err(ParseError::at_token(i, line, token, expected));
+                    }
+                    _ => {
+                        let expected = "`.`, `#` or the guard `^`";
+                        return Err(ParseError::at_token(i, line, token, expected));
+                    }
+                }
+            }
+        }
+        if !found_guard {
+            return Err(ParseError::at_end_of_input(input, "the guard `^`"));
+        }
+        Ok(grid)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<Answer, SolveError> {
+        let mut grid = grid.clone();
+        mark_visited(&mut grid);
+        Ok(grid.into_iter().filter(|c| *c == 'X').count().into())
+    }
+
+    fn part2(grid: &Self::Input) -> Result<Answer, SolveError> {
+        let mut grid = grid.clone();
+        let loops = (0..grid.len())
+            .map(|i| {
+                let mut found_loop = false;
+                if grid[i] == '.' {
+                    grid[i] = '#';
+                    found_loop = has_loop(&grid);
+                    grid[i] = '.';
+                }
+                found_loop
+            })
+            .filter(|b| *b)
+            .count();
+        Ok(loops.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotate_vector() {
+        let vector: Vector = [2, 1];
+        assert_eq!([-1, 2], quarter_turn_clockwise(vector));
+    }
+
+    const SAMPLE1: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    #[test]
+    fn sample_part1() {
+        assert_eq!(Day06::part1(&Day06::parse(SAMPLE1).unwrap()).unwrap(), 41);
+    }
+
+    #[test]
+    fn sample_part2() {
+        assert_eq!(Day06::part2(&Day06::parse(SAMPLE1).unwrap()).unwrap(), 6);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Day06::parse("..#\n.^.\n^..\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        let error = Day06::parse("..#\n.>.\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert!(Day06::parse("..#\n...\n").is_err());
+    }
+}